@@ -0,0 +1,357 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde_json::{Map, Value, json};
+
+use crate::{
+    cli::run_all::format_duration,
+    solution::solution::{RunError, Runner},
+    util::util::InputConfig,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub warmup: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            runs: 10,
+            warmup: 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / sorted.len() as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Timings of `parse` or of one part, named the way the baseline file keys them.
+pub struct Measurement {
+    pub name: String,
+    pub stats: Stats,
+}
+
+pub struct DayBench {
+    pub day: String,
+    pub measurements: Result<Vec<Measurement>, RunError>,
+}
+
+fn sample<T>(options: BenchOptions, mut run: impl FnMut() -> T) -> (T, Stats) {
+    for _ in 0..options.warmup {
+        run();
+    }
+    let mut samples = Vec::with_capacity(options.runs);
+    let mut last = None;
+    for _ in 0..options.runs.max(1) {
+        let start = Instant::now();
+        let result = run();
+        samples.push(start.elapsed());
+        last = Some(result);
+    }
+    (last.unwrap(), Stats::of(&samples))
+}
+
+/// Times parsing and every implemented part separately, parts reuse one parsed input.
+pub fn bench_day(
+    solution: &dyn Runner,
+    config: &InputConfig,
+    file: &str,
+    options: BenchOptions,
+) -> DayBench {
+    let measurements = config
+        .read(&solution.name(), file)
+        .map_err(RunError::from)
+        .and_then(|raw| {
+            let (parsed, parse) = sample(options, || solution.parse_input(&raw));
+            let parsed = parsed.map_err(RunError::Parse)?;
+            let mut measurements = vec![Measurement {
+                name: "parse".to_string(),
+                stats: parse,
+            }];
+            for part in solution.parts() {
+                let (answer, stats) = sample(options, || solution.solve(parsed.as_ref(), *part));
+                answer.map_err(|error| RunError::Solve(*part, error))?;
+                measurements.push(Measurement {
+                    name: format!("part{}", part),
+                    stats,
+                });
+            }
+            Ok(measurements)
+        });
+    DayBench {
+        day: solution.name(),
+        measurements,
+    }
+}
+
+#[derive(Debug)]
+pub enum BaselineError {
+    Unreadable { path: PathBuf, source: io::Error },
+    Invalid { path: PathBuf, message: String },
+}
+
+impl fmt::Display for BaselineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BaselineError::Unreadable { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            BaselineError::Invalid { path, message } => {
+                write!(f, "invalid baseline {}: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl Error for BaselineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BaselineError::Unreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Median times keyed by day and measurement name.
+pub type Baseline = BTreeMap<(String, String), Duration>;
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+/// `{"day4": {"parse": {"min_ns": .., "median_ns": .., ..}, "part1": {..}}}`, days that could
+/// not be run are left out.
+pub fn to_json(benches: &[DayBench]) -> Value {
+    let mut days = Map::new();
+    for bench in benches {
+        if let Ok(measurements) = &bench.measurements {
+            let mut entries = Map::new();
+            for measurement in measurements {
+                let stats = measurement.stats;
+                entries.insert(
+                    measurement.name.clone(),
+                    json!({
+                        "min_ns": nanos(stats.min),
+                        "median_ns": nanos(stats.median),
+                        "mean_ns": nanos(stats.mean),
+                        "stddev_ns": nanos(stats.stddev),
+                    }),
+                );
+            }
+            days.insert(bench.day.clone(), Value::Object(entries));
+        }
+    }
+    Value::Object(days)
+}
+
+pub fn save_baseline(benches: &[DayBench], path: &Path) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(&to_json(benches))?;
+    fs::write(path, json + "\n")
+}
+
+pub fn parse_baseline(text: &str, path: &Path) -> Result<Baseline, BaselineError> {
+    let invalid = |message: String| BaselineError::Invalid {
+        path: path.to_path_buf(),
+        message,
+    };
+    let value = serde_json::from_str::<Value>(text).map_err(|error| invalid(error.to_string()))?;
+    let days = value
+        .as_object()
+        .ok_or_else(|| invalid("expected an object of days".to_string()))?;
+    let mut baseline = Baseline::new();
+    for (day, entries) in days {
+        let entries = entries
+            .as_object()
+            .ok_or_else(|| invalid(format!("`{}` should hold measurements", day)))?;
+        for (name, stats) in entries {
+            let median = stats
+                .get("median_ns")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| invalid(format!("`{}.{}` has no median_ns", day, name)))?;
+            baseline.insert((day.clone(), name.clone()), Duration::from_nanos(median));
+        }
+    }
+    Ok(baseline)
+}
+
+pub fn load_baseline(path: &Path) -> Result<Baseline, BaselineError> {
+    let text = fs::read_to_string(path).map_err(|source| BaselineError::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_baseline(&text, path)
+}
+
+/// Change of the median against the baseline, `0.25` is 25% slower.
+pub fn change(baseline: Duration, current: Duration) -> f64 {
+    if baseline.is_zero() {
+        return 0.0;
+    }
+    current.as_secs_f64() / baseline.as_secs_f64() - 1.0
+}
+
+/// Prints one row per measurement and returns how many are slower than the baseline by
+/// more than `threshold` percent.
+pub fn write_table(
+    benches: &[DayBench],
+    baseline: Option<&Baseline>,
+    threshold: u32,
+    out: &mut impl Write,
+) -> io::Result<usize> {
+    writeln!(
+        out,
+        "{:<6} {:<6} {:>12} {:>12} {:>12} {:>12}{}",
+        "day",
+        "step",
+        "min",
+        "median",
+        "mean",
+        "stddev",
+        if baseline.is_some() {
+            "  vs baseline"
+        } else {
+            ""
+        }
+    )?;
+    let mut regressions = 0;
+    for bench in benches {
+        let measurements = match &bench.measurements {
+            Ok(measurements) => measurements,
+            Err(error) => {
+                let reason = error
+                    .to_string()
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                writeln!(out, "{:<6} {:<6} {}", bench.day, "-", reason)?;
+                continue;
+            }
+        };
+        for measurement in measurements {
+            let stats = measurement.stats;
+            write!(
+                out,
+                "{:<6} {:<6} {:>12} {:>12} {:>12} {:>12}",
+                bench.day,
+                measurement.name,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.mean),
+                format_duration(stats.stddev)
+            )?;
+            let key = (bench.day.clone(), measurement.name.clone());
+            match baseline.map(|baseline| baseline.get(&key)) {
+                Some(Some(previous)) => {
+                    let change = change(*previous, stats.median);
+                    let regressed = change * 100.0 > threshold as f64;
+                    if regressed {
+                        regressions += 1;
+                    }
+                    writeln!(
+                        out,
+                        "  {:+.1}%{}",
+                        change * 100.0,
+                        if regressed { " REGRESSION" } else { "" }
+                    )?;
+                }
+                Some(None) => writeln!(out, "  new")?,
+                None => writeln!(out)?,
+            }
+        }
+    }
+    Ok(regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::registry;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::of(&samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+
+        let single = Stats::of(&[Duration::from_millis(7)]);
+        assert_eq!(single.median, Duration::from_millis(7));
+        assert_eq!(single.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_baseline_round_trip_and_regressions() {
+        let options = BenchOptions { runs: 3, warmup: 1 };
+        let benches = vec![bench_day(
+            registry::find(4).unwrap(),
+            &InputConfig::default(),
+            "test.txt",
+            options,
+        )];
+        let names = benches[0]
+            .measurements
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|measurement| measurement.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["parse", "part1", "part2"]);
+
+        let text = to_json(&benches).to_string();
+        let mut baseline = parse_baseline(&text, Path::new("baseline.json")).unwrap();
+        assert_eq!(baseline.len(), 3);
+
+        // a baseline ten times faster than now makes every step a regression
+        for median in baseline.values_mut() {
+            *median /= 10;
+        }
+        let mut out = vec![];
+        let regressions = write_table(&benches, Some(&baseline), 10, &mut out).unwrap();
+        assert_eq!(regressions, 3);
+        assert!(String::from_utf8(out).unwrap().contains("REGRESSION"));
+
+        assert!(parse_baseline("{\"day4\": {\"parse\": {}}}", Path::new("x")).is_err());
+        assert!(parse_baseline("[", Path::new("x")).is_err());
+    }
+}
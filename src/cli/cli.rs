@@ -0,0 +1,785 @@
+use std::{
+    fmt,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use crate::{
+    cli::{
+        bench::{self, BaselineError, BenchOptions},
+        run_all,
+        scaffold::{self, ScaffoldError},
+    },
+    solution::{
+        answers::{self, ManifestError, Outcome},
+        registry,
+        solution::{Part, Runner, SolutionError},
+    },
+    util::{
+        fetch::{self, FetchError, Fetched, UreqClient},
+        util::{InputConfig, InputError},
+    },
+};
+
+pub const HELP: &str = "\
+Advent of Code 2024 solutions
+
+Usage: Advent_of_Code_2024 [--input-dir <dir>] <command>
+
+Commands:
+  run <day> [--part 1|2|both] [--input <file>]
+          Solve a day, `day` can be `4` or `day4`. Defaults to the parts the day implements
+          on input.txt, `--input -` reads the puzzle from stdin
+  run-all [--input <file>] [--parallel]
+          Solve every day with at least one part and print answers with parse and solve times
+  bench [<day>] [--runs <n>] [--warmup <n>] [--input <file>]
+        [--save <file>] [--baseline <file>] [--threshold <percent>]
+          Time parse and every part over several runs and print min/median/mean/stddev,
+          `--save` writes the timings as a JSON baseline, `--baseline` compares medians
+          against one and fails when a step is more than `--threshold` (10) percent slower,
+          like `run-all` it also fails when a day cannot be run
+  verify [--answers <file>]
+          Check every answer recorded in answers.toml, inputs that are missing are skipped
+  fetch <day>
+          Download input.txt for a day into the input directory unless it is already there,
+          the session token is read from AOC_SESSION or .aoc-session
+  new <day>
+          Create src/<day> with a solution skeleton and empty inputs and register it
+  list    Show the implemented days and parts
+  help    Show this message
+
+Options:
+  --input-dir <dir>  Read inputs from <dir>/<day>/<file> (also AOC_INPUT_DIR)
+  -h, --help         Show this message
+";
+
+const DEFAULT_INPUT: &str = fetch::INPUT_FILE;
+const DEFAULT_THRESHOLD: u32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartSelection {
+    One,
+    Two,
+    Both,
+}
+
+impl PartSelection {
+    pub fn parts(&self) -> Vec<Part> {
+        match self {
+            PartSelection::One => vec![Part::One],
+            PartSelection::Two => vec![Part::Two],
+            PartSelection::Both => Part::ALL.to_vec(),
+        }
+    }
+
+    fn parse(value: &str) -> Result<PartSelection, CliError> {
+        match value {
+            "1" => Ok(PartSelection::One),
+            "2" => Ok(PartSelection::Two),
+            "both" => Ok(PartSelection::Both),
+            _ => Err(CliError::Usage(format!(
+                "invalid part `{}`, expected 1, 2 or both",
+                value
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        day: String,
+        parts: PartSelection,
+        input: String,
+    },
+    RunAll {
+        input: String,
+        parallel: bool,
+    },
+    Bench {
+        day: Option<String>,
+        input: String,
+        options: BenchOptions,
+        save: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+        threshold: u32,
+    },
+    Verify {
+        answers: PathBuf,
+    },
+    Fetch {
+        day: String,
+    },
+    New {
+        day: String,
+    },
+    List,
+    Help,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cli {
+    pub input_dir: Option<PathBuf>,
+    pub command: Command,
+}
+
+impl Cli {
+    pub fn input_config(&self) -> InputConfig {
+        match &self.input_dir {
+            Some(dir) => InputConfig::new(Some(dir.clone())),
+            None => InputConfig::from_env(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    UnknownDay(String),
+    Input(InputError),
+    Parse(SolutionError),
+    Solve(Part, SolutionError),
+    Incomplete(usize),
+    Manifest(ManifestError),
+    WrongAnswers(usize),
+    Baseline(BaselineError),
+    Regressions(usize),
+    Fetch(FetchError),
+    Scaffold(ScaffoldError),
+    Io(io::Error),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) | CliError::UnknownDay(_) => 2,
+            CliError::Input(_)
+            | CliError::Parse(_)
+            | CliError::Solve(..)
+            | CliError::Incomplete(_)
+            | CliError::Manifest(_)
+            | CliError::WrongAnswers(_)
+            | CliError::Baseline(_)
+            | CliError::Regressions(_)
+            | CliError::Fetch(_)
+            | CliError::Scaffold(_)
+            | CliError::Io(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}", message),
+            CliError::UnknownDay(day) => {
+                write!(f, "there is no solution for `{}`, see `list`", day)
+            }
+            CliError::Input(error) => write!(f, "{}", error),
+            CliError::Parse(error) => write!(f, "invalid input: {}", error),
+            CliError::Solve(part, error) => write!(f, "part {} failed: {}", part, error),
+            CliError::Incomplete(failed) => write!(f, "{} days could not be run", failed),
+            CliError::Manifest(error) => write!(f, "{}", error),
+            CliError::WrongAnswers(wrong) => write!(f, "{} answers do not match", wrong),
+            CliError::Baseline(error) => write!(f, "{}", error),
+            CliError::Regressions(slower) => {
+                write!(f, "{} steps are slower than the baseline", slower)
+            }
+            CliError::Fetch(error) => write!(f, "{}", error),
+            CliError::Scaffold(error) => write!(f, "{}", error),
+            CliError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<InputError> for CliError {
+    fn from(error: InputError) -> Self {
+        CliError::Input(error)
+    }
+}
+
+impl From<ManifestError> for CliError {
+    fn from(error: ManifestError) -> Self {
+        CliError::Manifest(error)
+    }
+}
+
+impl From<BaselineError> for CliError {
+    fn from(error: BaselineError) -> Self {
+        CliError::Baseline(error)
+    }
+}
+
+impl From<FetchError> for CliError {
+    fn from(error: FetchError) -> Self {
+        CliError::Fetch(error)
+    }
+}
+
+impl From<ScaffoldError> for CliError {
+    fn from(error: ScaffoldError) -> Self {
+        CliError::Scaffold(error)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Io(error)
+    }
+}
+
+fn value_of(flag: &str, value: Option<String>) -> Result<String, CliError> {
+    value.ok_or_else(|| CliError::Usage(format!("`{}` needs a value", flag)))
+}
+
+fn parse_run(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut day = None;
+    let mut parts = PartSelection::Both;
+    let mut input = DEFAULT_INPUT.to_string();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parts = PartSelection::parse(&value_of(&arg, args.next())?)?,
+            "--input" | "-i" => input = value_of(&arg, args.next())?,
+            "-" => input = arg,
+            _ if arg.starts_with('-') => {
+                return Err(CliError::Usage(format!("unknown option `{}`", arg)));
+            }
+            _ if day.is_none() => day = Some(arg),
+            _ => return Err(CliError::Usage(format!("unexpected argument `{}`", arg))),
+        }
+    }
+    let day = day.ok_or_else(|| CliError::Usage("`run` needs a day".to_string()))?;
+    Ok(Command::Run { day, parts, input })
+}
+
+fn parse_run_all(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut input = DEFAULT_INPUT.to_string();
+    let mut parallel = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => input = value_of(&arg, args.next())?,
+            "--parallel" => parallel = true,
+            _ => return Err(CliError::Usage(format!("unexpected argument `{}`", arg))),
+        }
+    }
+    Ok(Command::RunAll { input, parallel })
+}
+
+fn number_of<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, CliError> {
+    let value = value_of(flag, value)?;
+    value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("`{}` expects a number, got `{}`", flag, value)))
+}
+
+fn parse_bench(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut day = None;
+    let mut input = DEFAULT_INPUT.to_string();
+    let mut options = BenchOptions::default();
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = DEFAULT_THRESHOLD;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => options.runs = number_of(&arg, args.next())?,
+            "--warmup" => options.warmup = number_of(&arg, args.next())?,
+            "--input" | "-i" => input = value_of(&arg, args.next())?,
+            "--save" => save = Some(PathBuf::from(value_of(&arg, args.next())?)),
+            "--baseline" => baseline = Some(PathBuf::from(value_of(&arg, args.next())?)),
+            "--threshold" => threshold = number_of(&arg, args.next())?,
+            _ if arg.starts_with('-') => {
+                return Err(CliError::Usage(format!("unknown option `{}`", arg)));
+            }
+            _ if day.is_none() => day = Some(arg),
+            _ => return Err(CliError::Usage(format!("unexpected argument `{}`", arg))),
+        }
+    }
+    if options.runs == 0 {
+        return Err(CliError::Usage("`--runs` must be at least 1".to_string()));
+    }
+    Ok(Command::Bench {
+        day,
+        input,
+        options,
+        save,
+        baseline,
+        threshold,
+    })
+}
+
+fn parse_verify(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut answers = answers::manifest_path();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = PathBuf::from(value_of(&arg, args.next())?),
+            _ => return Err(CliError::Usage(format!("unexpected argument `{}`", arg))),
+        }
+    }
+    Ok(Command::Verify { answers })
+}
+
+fn parse_day_argument(
+    command: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<String, CliError> {
+    match args.next() {
+        Some(day) if !day.starts_with('-') => Ok(day),
+        Some(arg) => Err(CliError::Usage(format!("unexpected argument `{}`", arg))),
+        None => Err(CliError::Usage(format!("`{}` needs a day", command))),
+    }
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Cli, CliError> {
+    let mut args = args.into_iter();
+    let mut input_dir = None;
+    while let Some(arg) = args.next() {
+        let command = match arg.as_str() {
+            "--input-dir" => {
+                input_dir = Some(PathBuf::from(value_of(&arg, args.next())?));
+                continue;
+            }
+            "-h" | "--help" | "help" => Command::Help,
+            "list" => Command::List,
+            "run" => parse_run(&mut args)?,
+            "run-all" => parse_run_all(&mut args)?,
+            "bench" => parse_bench(&mut args)?,
+            "verify" => parse_verify(&mut args)?,
+            "fetch" => Command::Fetch {
+                day: parse_day_argument(&arg, &mut args)?,
+            },
+            "new" => Command::New {
+                day: parse_day_argument(&arg, &mut args)?,
+            },
+            _ => return Err(CliError::Usage(format!("unknown command `{}`", arg))),
+        };
+        if let Some(extra) = args.next()
+            && command != Command::Help
+        {
+            return Err(CliError::Usage(format!("unexpected argument `{}`", extra)));
+        }
+        return Ok(Cli { input_dir, command });
+    }
+    Err(CliError::Usage("missing command".to_string()))
+}
+
+pub fn find_day(day: &str) -> Result<&'static dyn Runner, CliError> {
+    registry::find_by_name(day).ok_or_else(|| CliError::UnknownDay(day.to_string()))
+}
+
+fn run(
+    cli: &Cli,
+    day: &str,
+    parts: PartSelection,
+    input: &str,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    let solution = find_day(day)?;
+    // like run-all, `both` means the parts the day implements
+    let parts = match parts {
+        PartSelection::Both => solution.parts().to_vec(),
+        selected => selected.parts(),
+    };
+    if let Some(part) = parts.iter().find(|part| !solution.parts().contains(part)) {
+        return Err(CliError::Usage(format!(
+            "{} has no part {}, see `list`",
+            solution.name(),
+            part
+        )));
+    }
+    let raw = cli.input_config().read(&solution.name(), input)?;
+    let parsed = solution.parse_input(&raw).map_err(CliError::Parse)?;
+    for part in parts {
+        let answer = solution
+            .solve(parsed.as_ref(), part)
+            .map_err(|error| CliError::Solve(part, error))?;
+        writeln!(out, "{} part {}: {}", solution.name(), part, answer)?;
+    }
+    Ok(())
+}
+
+fn run_all(cli: &Cli, input: &str, parallel: bool, out: &mut impl Write) -> Result<(), CliError> {
+    let start = Instant::now();
+    let reports = run_all::run_all(&cli.input_config(), input, parallel);
+    run_all::write_table(&reports, start.elapsed(), out)?;
+    match reports.iter().filter(|report| report.failed()).count() {
+        0 => Ok(()),
+        failed => Err(CliError::Incomplete(failed)),
+    }
+}
+
+struct BenchRequest<'a> {
+    day: Option<&'a str>,
+    input: &'a str,
+    options: BenchOptions,
+    save: Option<&'a Path>,
+    baseline: Option<&'a Path>,
+    threshold: u32,
+}
+
+fn bench(cli: &Cli, request: BenchRequest, out: &mut impl Write) -> Result<(), CliError> {
+    let solutions = match request.day {
+        Some(day) => vec![find_day(day)?],
+        None => registry::implemented(),
+    };
+    // read the baseline first so a typo in its path does not waste a whole run
+    let baseline = request.baseline.map(bench::load_baseline).transpose()?;
+    let config = cli.input_config();
+    let benches = solutions
+        .iter()
+        .map(|solution| bench::bench_day(*solution, &config, request.input, request.options))
+        .collect::<Vec<_>>();
+    let regressions = bench::write_table(&benches, baseline.as_ref(), request.threshold, out)?;
+    if let Some(path) = request.save {
+        bench::save_baseline(&benches, path)?;
+        writeln!(out, "saved baseline to {}", path.display())?;
+    }
+    // a day that could not be timed must not pass as a day with no regressions
+    let failed = benches
+        .iter()
+        .filter(|bench| bench.measurements.is_err())
+        .count();
+    match (failed, regressions) {
+        (0, 0) => Ok(()),
+        (0, slower) => Err(CliError::Regressions(slower)),
+        (failed, _) => Err(CliError::Incomplete(failed)),
+    }
+}
+
+fn verify(cli: &Cli, manifest: &Path, out: &mut impl Write) -> Result<(), CliError> {
+    let expected = answers::load_manifest(manifest)?;
+    let verifications = answers::verify(&expected, &cli.input_config());
+    let (mut correct, mut wrong, mut skipped) = (0, 0, 0);
+    for verification in &verifications {
+        let expected = &verification.expected;
+        match &verification.outcome {
+            Outcome::Correct => {
+                correct += 1;
+                writeln!(out, "ok       {}: {}", expected, expected.answer)?;
+            }
+            Outcome::Wrong(answer) => {
+                wrong += 1;
+                writeln!(
+                    out,
+                    "FAILED   {}: expected {}, got {}",
+                    expected, expected.answer, answer
+                )?;
+            }
+            Outcome::Failed(error) => {
+                wrong += 1;
+                writeln!(out, "FAILED   {}: {}", expected, error)?;
+            }
+            Outcome::Skipped(error) => {
+                skipped += 1;
+                let reason = error
+                    .to_string()
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                writeln!(out, "skipped  {}: {}", expected, reason)?;
+            }
+        }
+    }
+    writeln!(out, "{} ok, {} failed, {} skipped", correct, wrong, skipped)?;
+    match wrong {
+        0 => Ok(()),
+        wrong => Err(CliError::WrongAnswers(wrong)),
+    }
+}
+
+/// Unlike `run`, `fetch` and `new` take any day of the calendar, solved or not.
+fn day_number(day: &str) -> Result<u8, CliError> {
+    registry::day_number(day)
+        .ok_or_else(|| CliError::Usage(format!("`{}` is not a day between 1 and 25", day)))
+}
+
+fn fetch(cli: &Cli, day: &str, out: &mut impl Write) -> Result<(), CliError> {
+    let number = day_number(day)?;
+    let fetched = fetch::fetch_input(
+        &UreqClient,
+        &cli.input_config(),
+        number,
+        fetch::read_session,
+    )?;
+    match fetched {
+        Fetched::Cached(path) => {
+            writeln!(out, "day{} input already at {}", number, path.display())?
+        }
+        Fetched::Downloaded(path) => {
+            writeln!(out, "day{} input saved to {}", number, path.display())?
+        }
+    }
+    Ok(())
+}
+
+fn new(day: &str, out: &mut impl Write) -> Result<(), CliError> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for path in scaffold::scaffold(root, day_number(day)?)? {
+        writeln!(out, "created {}", path.display())?;
+    }
+    writeln!(out, "registered in src/lib.rs and src/solution/registry.rs")?;
+    Ok(())
+}
+
+fn list(out: &mut impl Write) -> Result<(), CliError> {
+    for solution in registry::SOLUTIONS {
+        let parts = solution
+            .parts()
+            .iter()
+            .map(|part| part.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let parts = if parts.is_empty() { "none" } else { &parts };
+        writeln!(out, "{}  parts: {}", solution.name(), parts)?;
+    }
+    Ok(())
+}
+
+pub fn execute(cli: &Cli, out: &mut impl Write) -> Result<(), CliError> {
+    match &cli.command {
+        Command::Run { day, parts, input } => run(cli, day, *parts, input, out),
+        Command::RunAll { input, parallel } => run_all(cli, input, *parallel, out),
+        Command::Bench {
+            day,
+            input,
+            options,
+            save,
+            baseline,
+            threshold,
+        } => bench(
+            cli,
+            BenchRequest {
+                day: day.as_deref(),
+                input,
+                options: *options,
+                save: save.as_deref(),
+                baseline: baseline.as_deref(),
+                threshold: *threshold,
+            },
+            out,
+        ),
+        Command::Verify { answers } => verify(cli, answers, out),
+        Command::Fetch { day } => fetch(cli, day, out),
+        Command::New { day } => new(day, out),
+        Command::List => list(out),
+        Command::Help => Ok(write!(out, "{}", HELP)?),
+    }
+}
+
+pub fn main(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let result = parse_args(args).and_then(|cli| execute(&cli, &mut io::stdout().lock()));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            if let CliError::Usage(_) = error {
+                eprintln!("\n{}", HELP);
+            }
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    /// Writes `contents` to `file` under a temporary input directory named `directory` and
+    /// returns a parser for command lines that read their inputs from there.
+    fn with_input(directory: &str, file: &str, contents: &str) -> impl Fn(&str) -> Cli {
+        let directory = std::env::temp_dir().join(directory);
+        let path = directory.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+        move |line| {
+            let mut line = args(line);
+            line.splice(
+                0..0,
+                ["--input-dir".to_string(), directory.display().to_string()],
+            );
+            parse_args(line).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_parse_run() {
+        let cli = parse_args(args(
+            "--input-dir inputs run day4 --part 2 --input test.txt",
+        ))
+        .unwrap();
+        assert_eq!(cli.input_dir, Some(PathBuf::from("inputs")));
+        assert_eq!(
+            cli.command,
+            Command::Run {
+                day: "day4".to_string(),
+                parts: PartSelection::Two,
+                input: "test.txt".to_string(),
+            }
+        );
+        let cli = parse_args(args("run-all --parallel")).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::RunAll {
+                input: DEFAULT_INPUT.to_string(),
+                parallel: true,
+            }
+        );
+        let cli = parse_args(args("bench day6 --runs 3 --baseline base.json")).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Bench {
+                day: Some("day6".to_string()),
+                input: DEFAULT_INPUT.to_string(),
+                options: BenchOptions { runs: 3, warmup: 2 },
+                save: None,
+                baseline: Some(PathBuf::from("base.json")),
+                threshold: DEFAULT_THRESHOLD,
+            }
+        );
+        let cli = parse_args(args("run 5")).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Run {
+                day: "5".to_string(),
+                parts: PartSelection::Both,
+                input: DEFAULT_INPUT.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        for line in [
+            "",
+            "fly",
+            "run",
+            "run day4 --part 3",
+            "run day4 --input",
+            "list extra",
+            "fetch",
+            "fetch 4 5",
+            "new",
+            "bench --runs many",
+            "bench --runs 0",
+        ] {
+            let error = parse_args(args(line)).unwrap_err();
+            assert_eq!(error.exit_code(), 2, "`{}` should be a usage error", line);
+        }
+    }
+
+    #[test]
+    fn test_run_and_list() {
+        let cli = parse_args(args("run day4 --input test.txt")).unwrap();
+        let mut out = vec![];
+        execute(&cli, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day4 part 1: 18\nday4 part 2: 9\n"
+        );
+
+        let mut out = vec![];
+        execute(&parse_args(args("list")).unwrap(), &mut out).unwrap();
+        let listed = String::from_utf8(out).unwrap();
+        assert!(listed.contains("day4  parts: 1, 2"));
+        assert!(listed.contains("day7  parts: 1\n"));
+
+        // day7 only implements part 1, so `both` runs that one and part 2 is refused
+        let mut out = vec![];
+        execute(
+            &parse_args(args("run day7 --input test.txt")).unwrap(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "day7 part 1: 3749\n");
+        let cli = parse_args(args("run day7 --part 2 --input test.txt")).unwrap();
+        let error = execute(&cli, &mut vec![]).unwrap_err();
+        assert_eq!(error.exit_code(), 2);
+        assert_eq!(error.to_string(), "day7 has no part 2, see `list`");
+
+        let cli = parse_args(args("fetch day26")).unwrap();
+        assert!(matches!(
+            execute(&cli, &mut vec![]),
+            Err(CliError::Usage(_))
+        ));
+
+        let cli = parse_args(args("run day25")).unwrap();
+        assert!(matches!(
+            execute(&cli, &mut vec![]),
+            Err(CliError::UnknownDay(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_input_is_reported() {
+        let with_dir = with_input("aoc_2024_invalid_input", "day5/invalid.txt", "1|2\n2|x\n");
+
+        let error = execute(&with_dir("run day5 --input invalid.txt"), &mut vec![]).unwrap_err();
+        assert!(matches!(error, CliError::Parse(_)));
+        assert_eq!(error.exit_code(), 1);
+        assert_eq!(
+            error.to_string(),
+            "invalid input: line 2: \"x\" is not a page number"
+        );
+
+        let mut out = vec![];
+        // the other days have no invalid.txt, only the day5 row matters here
+        let error = execute(&with_dir("run-all --input invalid.txt"), &mut out).unwrap_err();
+        assert!(matches!(error, CliError::Incomplete(_)));
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains("day5   -    invalid input: line 2")
+        );
+
+        let mut out = vec![];
+        let error = execute(
+            &with_dir("bench day5 --runs 1 --input invalid.txt"),
+            &mut out,
+        )
+        .unwrap_err();
+        assert!(matches!(error, CliError::Incomplete(1)));
+        assert_eq!(error.exit_code(), 1);
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains("day5   -      invalid input: line 2")
+        );
+    }
+
+    #[test]
+    fn test_cyclic_rules_are_reported() {
+        let with_dir = with_input(
+            "aoc_2024_cyclic_rules",
+            "day5/cyclic.txt",
+            "1|2\n2|1\n\n1,2\n",
+        );
+
+        // the rules parse, but no order of the pages follows them
+        let error = execute(
+            &with_dir("run day5 --input cyclic.txt --part 2"),
+            &mut vec![],
+        )
+        .unwrap_err();
+        assert!(matches!(error, CliError::Solve(Part::Two, _)));
+        assert_eq!(error.exit_code(), 1);
+        assert_eq!(
+            error.to_string(),
+            "part 2 failed: no order follows every rule, they go round in a circle: 1 -> 2 -> 1"
+        );
+
+        let mut out = vec![];
+        let error = execute(&with_dir("run-all --input cyclic.txt"), &mut out).unwrap_err();
+        assert!(matches!(error, CliError::Incomplete(_)));
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains("day5   2    no order follows every rule")
+        );
+    }
+}
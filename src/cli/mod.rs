@@ -0,0 +1,4 @@
+pub mod bench;
+pub mod cli;
+pub mod run_all;
+pub mod scaffold;
@@ -0,0 +1,217 @@
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    solution::{
+        registry,
+        solution::{Answer, Part, RunError, Runner, SolutionError},
+    },
+    util::util::InputConfig,
+};
+
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Result<Answer, SolutionError>,
+    pub solve_time: Duration,
+}
+
+pub struct DayReport {
+    pub day: String,
+    pub parse_time: Duration,
+    pub parts: Result<Vec<PartReport>, RunError>,
+}
+
+impl DayReport {
+    pub fn total_time(&self) -> Duration {
+        let solve_time = match &self.parts {
+            Ok(parts) => parts.iter().map(|part| part.solve_time).sum(),
+            Err(_) => Duration::ZERO,
+        };
+        self.parse_time + solve_time
+    }
+
+    /// Whether the input could not be read or parsed, or a part could not be solved.
+    pub fn failed(&self) -> bool {
+        match &self.parts {
+            Ok(parts) => parts.iter().any(|part| part.answer.is_err()),
+            Err(_) => true,
+        }
+    }
+}
+
+pub fn run_day(solution: &dyn Runner, config: &InputConfig, file: &str) -> DayReport {
+    let raw = match config.read(&solution.name(), file) {
+        Ok(raw) => raw,
+        Err(error) => {
+            return DayReport {
+                day: solution.name(),
+                parse_time: Duration::ZERO,
+                parts: Err(error.into()),
+            };
+        }
+    };
+    let start = Instant::now();
+    let parsed = solution.parse_input(&raw);
+    let parse_time = start.elapsed();
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return DayReport {
+                day: solution.name(),
+                parse_time,
+                parts: Err(RunError::Parse(error)),
+            };
+        }
+    };
+    let parts = solution
+        .parts()
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = solution.solve(parsed.as_ref(), *part);
+            PartReport {
+                part: *part,
+                answer,
+                solve_time: start.elapsed(),
+            }
+        })
+        .collect();
+    DayReport {
+        day: solution.name(),
+        parse_time,
+        parts: Ok(parts),
+    }
+}
+
+/// Runs every implemented day, in registry order even when `parallel` spreads them over threads.
+pub fn run_all(config: &InputConfig, file: &str, parallel: bool) -> Vec<DayReport> {
+    let solutions = registry::implemented();
+    if parallel {
+        solutions
+            .par_iter()
+            .map(|solution| run_day(*solution, config, file))
+            .collect()
+    } else {
+        solutions
+            .iter()
+            .map(|solution| run_day(*solution, config, file))
+            .collect()
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+/// Errors can span lines, a table row cannot.
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn write_table(
+    reports: &[DayReport],
+    wall_time: Duration,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "{:<6} {:<4} {:>20} {:>12} {:>12}",
+        "day", "part", "answer", "parse", "solve"
+    )?;
+    for report in reports {
+        match &report.parts {
+            Ok(parts) => {
+                for part in parts {
+                    match &part.answer {
+                        Ok(answer) => writeln!(
+                            out,
+                            "{:<6} {:<4} {:>20} {:>12} {:>12}",
+                            report.day,
+                            part.part,
+                            answer.to_string(),
+                            format_duration(report.parse_time),
+                            format_duration(part.solve_time)
+                        )?,
+                        Err(error) => writeln!(
+                            out,
+                            "{:<6} {:<4} {}",
+                            report.day,
+                            part.part,
+                            one_line(&error.to_string())
+                        )?,
+                    }
+                }
+            }
+            Err(error) => {
+                writeln!(
+                    out,
+                    "{:<6} {:<4} {}",
+                    report.day,
+                    "-",
+                    one_line(&error.to_string())
+                )?;
+            }
+        }
+    }
+    let total: Duration = reports.iter().map(|report| report.total_time()).sum();
+    writeln!(
+        out,
+        "total: {} (wall clock {})",
+        format_duration(total),
+        format_duration(wall_time)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::util::InputError;
+
+    #[test]
+    fn test_run_all_on_examples() {
+        let reports = run_all(&InputConfig::default(), "test.txt", true);
+        let days = reports
+            .iter()
+            .map(|report| report.day.as_str())
+            .collect::<Vec<_>>();
+        let implemented = registry::implemented()
+            .iter()
+            .map(|solution| solution.name())
+            .collect::<Vec<_>>();
+        assert_eq!(days, implemented);
+
+        let day4 = reports[0].parts.as_ref().unwrap();
+        assert_eq!(day4[0].answer.as_ref().unwrap(), &Answer::Number(18));
+        assert_eq!(day4[1].answer.as_ref().unwrap(), &Answer::Number(9));
+        // day7 only solves part 1, it gets no row for the unsolved part
+        let day7 = reports[3].parts.as_ref().unwrap();
+        assert_eq!(day7.len(), 1);
+
+        let mut out = vec![];
+        write_table(&reports, Duration::ZERO, &mut out).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        let rows = reports
+            .iter()
+            .map(|report| report.parts.as_ref().map_or(1, |parts| parts.len()))
+            .sum::<usize>();
+        assert_eq!(table.lines().count(), 1 + rows + 1);
+    }
+
+    #[test]
+    fn test_missing_input_is_reported() {
+        let report = run_day(
+            registry::SOLUTIONS[0],
+            &InputConfig::default(),
+            "missing.txt",
+        );
+        assert!(matches!(
+            report.parts,
+            Err(RunError::Input(InputError::Missing { .. }))
+        ));
+        assert_eq!(report.total_time(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+const MAX_WIDTH: usize = 100;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// `lib.rs` or `registry.rs` no longer has the shape the generator edits.
+    Unrecognized {
+        path: PathBuf,
+        expected: String,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            ScaffoldError::Unrecognized { path, expected } => write!(
+                f,
+                "could not register the day in {}, expected {}",
+                path.display(),
+                expected
+            ),
+        }
+    }
+}
+
+impl Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> ScaffoldError + '_ {
+    move |source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+fn unrecognized(path: &Path, expected: &str) -> ScaffoldError {
+    ScaffoldError::Unrecognized {
+        path: path.to_path_buf(),
+        expected: expected.to_string(),
+    }
+}
+
+pub fn module_source(day: u8) -> String {
+    r#"use crate::solution::solution::{Answer, Part, Solution, SolutionError};
+
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    const DAY: u8 = {N};
+    const PARTS: &'static [Part] = &[];
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, SolutionError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(_: &String) -> Result<Answer, SolutionError> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn part2(_: &String) -> Result<Answer, SolutionError> {
+        Ok(Answer::Unsolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::util::read_input;
+
+    fn read(file: &str) -> String {
+        Day{N}::parse(&read_input("day{N}", file).unwrap()).unwrap()
+    }
+
+    #[test]
+    #[ignore = "paste the example into test.txt and fill in its answers"]
+    fn test_example() {
+        let input = read("test.txt");
+        assert_eq!(Day{N}::part1(&input).unwrap(), Answer::Unsolved);
+        assert_eq!(Day{N}::part2(&input).unwrap(), Answer::Unsolved);
+    }
+
+    // the answers of input.txt belong in answers.toml, where `verify` checks them
+    #[test]
+    #[ignore = "fetch input.txt first"]
+    fn test_input() {
+        let input = read("input.txt");
+        Day{N}::part1(&input).unwrap();
+        Day{N}::part2(&input).unwrap();
+    }
+}
+"#
+    .replace("{N}", &day.to_string())
+}
+
+/// Lays `items` out the way rustfmt does: on one line when it fits, otherwise as many
+/// items per indented line as fit, each followed by a comma.
+fn wrap_list(open: &str, items: &[String], close: &str) -> String {
+    let single = format!("{}{}{}", open, items.join(", "), close);
+    if single.len() <= MAX_WIDTH {
+        return single;
+    }
+    let mut lines = vec![open.trim_end().to_string()];
+    let mut line = String::new();
+    for item in items {
+        if !line.is_empty() && 4 + line.len() + 1 + item.len() + 1 > MAX_WIDTH {
+            lines.push(format!("    {}", line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(item);
+        line.push(',');
+    }
+    lines.push(format!("    {}", line));
+    lines.push(close.trim_start().to_string());
+    lines.join("\n")
+}
+
+/// Orders `day4` before `day10`, anything else after the days.
+fn day_order(item: &str) -> (u32, String) {
+    let digits = item
+        .trim_start_matches(['&', 'D', 'd', 'a', 'y'])
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    (digits.parse().unwrap_or(u32::MAX), item.to_string())
+}
+
+/// Replaces the comma separated list between `open` and `close` with `items` plus `added`.
+fn add_to_list(source: &str, open: &str, close: &str, added: String) -> Option<String> {
+    let start = source.find(open)?;
+    let end = start + open.len() + source[start + open.len()..].find(close)?;
+    let mut items = source[start + open.len()..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+    items.push(added);
+    items.sort_by_key(|item| day_order(item));
+    Some(format!(
+        "{}{}{}",
+        &source[..start],
+        wrap_list(open, &items, close),
+        &source[end + close.len()..]
+    ))
+}
+
+fn register_module(lib: &Path, day: u8) -> Result<(), ScaffoldError> {
+    let source = fs::read_to_string(lib).map_err(io_error(lib))?;
+    let mut lines = source.lines().map(String::from).collect::<Vec<_>>();
+    let declaration = format!("pub mod day{};", day);
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod day"))
+        .map(|(index, line)| (index, day_order(&line["pub mod ".len()..]).0))
+        .collect::<Vec<_>>();
+    let index = match days.iter().find(|(_, number)| *number > day as u32) {
+        Some((index, _)) => *index,
+        None => match days.last() {
+            Some((index, _)) => index + 1,
+            None => return Err(unrecognized(lib, "`pub mod day<N>;` declarations")),
+        },
+    };
+    lines.insert(index, declaration);
+    fs::write(lib, lines.join("\n") + "\n").map_err(io_error(lib))
+}
+
+fn register_solution(registry: &Path, day: u8) -> Result<(), ScaffoldError> {
+    let source = fs::read_to_string(registry).map_err(io_error(registry))?;
+    let source = add_to_list(
+        &source,
+        "use crate::{",
+        "};",
+        format!("day{0}::day{0}::Day{0}", day),
+    )
+    .ok_or_else(|| unrecognized(registry, "a `use crate::{...};` import"))?;
+    let source = add_to_list(
+        &source,
+        "pub static SOLUTIONS: &[&dyn Runner] = &[",
+        "];",
+        format!("&Day{}", day),
+    )
+    .ok_or_else(|| unrecognized(registry, "the `SOLUTIONS` array"))?;
+    fs::write(registry, source).map_err(io_error(registry))
+}
+
+/// Creates `src/day<N>` under `root` with a solution skeleton and empty inputs, then
+/// declares it in `lib.rs` and adds it to the registry. Returns the created files.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let src = root.join("src");
+    let name = format!("day{}", day);
+    let directory = src.join(&name);
+    if directory.exists() {
+        return Err(ScaffoldError::Exists(directory));
+    }
+    let lib = src.join("lib.rs");
+    let registry = src.join("solution").join("registry.rs");
+    for path in [&lib, &registry] {
+        if !path.is_file() {
+            return Err(unrecognized(path, "an existing file"));
+        }
+    }
+
+    fs::create_dir_all(&directory).map_err(io_error(&directory))?;
+    let files = [
+        ("mod.rs", format!("pub mod {};\n", name)),
+        (&format!("{}.rs", name), module_source(day)),
+        ("test.txt", String::new()),
+        ("input.txt", String::new()),
+    ]
+    .into_iter()
+    .map(|(file, contents)| {
+        let path = directory.join(file);
+        fs::write(&path, contents).map_err(io_error(&path))?;
+        Ok(path)
+    })
+    .collect::<Result<Vec<_>, ScaffoldError>>()?;
+    register_module(&lib, day)?;
+    register_solution(&registry, day)?;
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_crate(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src").join("solution")).unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            "#![allow(non_snake_case)]\n\npub mod cli;\npub mod day4;\npub mod day7;\npub mod solution;\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/solution/registry.rs"),
+            "use crate::{day4::day4::Day4, day7::day7::Day7, solution::solution::Runner};\n\n\
+             pub static SOLUTIONS: &[&dyn Runner] = &[&Day4, &Day7];\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_scaffold_registers_the_day() {
+        let root = fake_crate("aoc_2024_scaffold");
+        let files = scaffold(&root, 12).unwrap();
+        assert_eq!(files.len(), 4);
+        assert_eq!(
+            fs::read_to_string(root.join("src/day12/mod.rs")).unwrap(),
+            "pub mod day12;\n"
+        );
+        assert!(
+            fs::read_to_string(root.join("src/day12/day12.rs"))
+                .unwrap()
+                .contains("impl Solution for Day12 {\n    const DAY: u8 = 12;")
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/day12/input.txt")).unwrap(),
+            ""
+        );
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod day7;\npub mod day12;\npub mod solution;"));
+        let registry = fs::read_to_string(root.join("src/solution/registry.rs")).unwrap();
+        assert!(registry.contains("day7::day7::Day7, day12::day12::Day12,"));
+        assert!(registry.contains("&Day7, &Day12]"));
+
+        // an earlier day is placed before the later ones
+        scaffold(&root, 1).unwrap();
+        let registry = fs::read_to_string(root.join("src/solution/registry.rs")).unwrap();
+        assert!(registry.contains("&[&Day1, &Day4"));
+
+        assert!(matches!(scaffold(&root, 12), Err(ScaffoldError::Exists(_))));
+    }
+
+    #[test]
+    fn test_wrap_list() {
+        let items = ["a".to_string(), "b".to_string()];
+        assert_eq!(wrap_list("&[", &items, "]"), "&[a, b]");
+        let long = (0..30).map(|n| format!("&Day{}", n)).collect::<Vec<_>>();
+        let wrapped = wrap_list("x = &[", &long, "];");
+        assert!(wrapped.starts_with("x = &[\n    &Day0, &Day1,"));
+        assert!(wrapped.ends_with(",\n];"));
+        assert!(wrapped.lines().all(|line| line.len() <= MAX_WIDTH));
+    }
+}
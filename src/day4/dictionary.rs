@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use rayon::prelude::*;
+
+use crate::{
+    day4::search::{self, Match, SearchOptions},
+    util::{
+        geometry::{Direction8, Point},
+        grid::Grid,
+    },
+};
+
+#[derive(Default)]
+struct TrieNode {
+    /// Few letters follow any prefix, a short list beats a map here.
+    children: Vec<(char, usize)>,
+    word: Option<usize>,
+}
+
+/// A prefix trie over a word list, built once and searched for all words at the same time.
+#[derive(Default)]
+pub struct Dictionary {
+    nodes: Vec<TrieNode>,
+    words: Vec<String>,
+    longest: usize,
+}
+
+impl Dictionary {
+    pub fn new<'a>(words: impl IntoIterator<Item = &'a str>) -> Dictionary {
+        let mut dictionary = Dictionary {
+            nodes: vec![TrieNode::default()],
+            ..Dictionary::default()
+        };
+        for word in words {
+            dictionary.insert(word);
+        }
+        dictionary
+    }
+
+    fn insert(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+        let mut node = 0;
+        for letter in word.chars() {
+            node = match self.child(node, letter) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((letter, child));
+                    child
+                }
+            };
+        }
+        if self.nodes[node].word.is_none() {
+            self.nodes[node].word = Some(self.words.len());
+            self.words.push(word.to_string());
+            self.longest = self.longest.max(word.chars().count());
+        }
+    }
+
+    fn child(&self, node: usize, letter: char) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(child_letter, _)| *child_letter == letter)
+            .map(|(_, child)| *child)
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+}
+
+/// `(word index, letters)` for every dictionary word that can be read from `start`.
+fn walk(
+    grid: &Grid<char>,
+    dictionary: &Dictionary,
+    start: Point,
+    direction: Direction8,
+    options: SearchOptions,
+    found: &mut Vec<(usize, usize)>,
+) {
+    let limit = if options.wrap_around {
+        dictionary.longest.min(search::period(grid, direction))
+    } else {
+        dictionary.longest
+    };
+    let (width, height) = (grid.width() as i32, grid.height() as i32);
+    let mut position = start;
+    let mut node = 0;
+    for length in 1..=limit {
+        let Some(letter) = grid.get(position) else {
+            return;
+        };
+        let Some(child) = dictionary.child(node, *letter) else {
+            return;
+        };
+        node = child;
+        if let Some(word) = dictionary.nodes[node].word {
+            found.push((word, length));
+        }
+        position += direction.delta();
+        if options.wrap_around {
+            position = Point::new(position.x.rem_euclid(width), position.y.rem_euclid(height));
+        }
+    }
+}
+
+/// Finds every dictionary word in one pass over the grid. Each word of the dictionary is a key
+/// of the result, with no matches when it does not appear.
+pub fn find_dictionary(
+    grid: &Grid<char>,
+    dictionary: &Dictionary,
+    options: SearchOptions,
+) -> BTreeMap<String, Vec<Match>> {
+    let directions = options.directions.directions();
+    let rows = (0..grid.height() as i32)
+        .into_par_iter()
+        .map(|y| {
+            let mut matches = vec![];
+            let mut found = vec![];
+            for x in 0..grid.width() as i32 {
+                let start = Point::new(x, y);
+                for direction in directions {
+                    found.clear();
+                    walk(grid, dictionary, start, *direction, options, &mut found);
+                    for (word, length) in &found {
+                        if !options.directions.for_length(*length).contains(direction) {
+                            continue;
+                        }
+                        matches.push((
+                            *word,
+                            Match {
+                                word: dictionary.words[*word].clone(),
+                                start,
+                                direction: *direction,
+                                cells: search::cells(
+                                    grid,
+                                    start,
+                                    *direction,
+                                    *length,
+                                    options.wrap_around,
+                                ),
+                            },
+                        ));
+                    }
+                }
+            }
+            matches
+        })
+        .collect::<Vec<_>>();
+
+    let mut grouped = dictionary
+        .words
+        .iter()
+        .map(|word| (word.clone(), vec![]))
+        .collect::<BTreeMap<_, _>>();
+    for (word, found) in rows.into_iter().flatten() {
+        grouped
+            .get_mut(&dictionary.words[word])
+            .expect("every word has a group")
+            .push(found);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::{
+        search::{self, DirectionSet},
+        testing::{grid, random_grid},
+    };
+
+    #[test]
+    fn test_dictionary_groups_words() {
+        let grid = grid("CAT\nAA.\nT.T\n");
+        let dictionary = Dictionary::new(["CAT", "CA", "TAC", "DOG", "CAT"]);
+        assert_eq!(dictionary.words(), ["CAT", "CA", "TAC", "DOG"]);
+
+        let found = find_dictionary(&grid, &dictionary, SearchOptions::default());
+        assert_eq!(found["CAT"].len(), 3);
+        assert_eq!(found["CA"].len(), 3);
+        assert_eq!(found["TAC"].len(), 3);
+        assert!(found["DOG"].is_empty());
+        assert!(
+            found["TAC"]
+                .iter()
+                .any(|found| found.cells
+                    == vec![Point::new(2, 2), Point::new(1, 1), Point::new(0, 0)])
+        );
+    }
+
+    #[test]
+    fn test_dictionary_agrees_with_word_search() {
+        let grid = random_grid(60, 40, b"ABCD", 7);
+        let words = ["AB", "ABC", "DCBA", "AAAA", "BAD", "C", "CAB"];
+        let dictionary = Dictionary::new(words);
+        for wrap_around in [false, true] {
+            for directions in [
+                DirectionSet::Orthogonal,
+                DirectionSet::Diagonal,
+                DirectionSet::All,
+            ] {
+                let options = SearchOptions {
+                    directions,
+                    wrap_around,
+                };
+                let found = find_dictionary(&grid, &dictionary, options);
+                for word in words {
+                    let mut expected = search::find_word(&grid, word, options);
+                    let mut actual = found[word].clone();
+                    let key = |found: &Match| (found.start, found.direction as usize);
+                    expected.sort_by_key(key);
+                    actual.sort_by_key(key);
+                    assert_eq!(actual, expected, "{} {:?}", word, options);
+                }
+            }
+        }
+    }
+}
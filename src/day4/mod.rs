@@ -1 +1,10 @@
 pub mod day4;
+pub mod dictionary;
+pub mod render;
+pub mod search;
+pub mod stats;
+pub mod stencil;
+pub mod stream;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,195 @@
+use std::io::{self, Write};
+
+use crate::{day4::search::Match, util::grid::Grid};
+
+type Rgb = (u8, u8, u8);
+
+const BACKGROUND: Rgb = (32, 32, 32);
+const UNUSED: Rgb = (110, 110, 110);
+
+/// A distinct color for every match, walking the hue circle by the golden ratio so neighbours in
+/// the list never look alike.
+pub fn color_of(index: usize) -> Rgb {
+    let hue = (index as f64 * 0.618_033_988_75).fract() * 6.0;
+    let (saturation, value) = (0.75, 0.95);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |c: f64| ((c + value - chroma) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// A grid together with the match that owns each cell, the last one when matches overlap.
+pub struct Highlighted<'a> {
+    grid: &'a Grid<char>,
+    owners: Grid<Option<usize>>,
+}
+
+impl<'a> Highlighted<'a> {
+    pub fn new(grid: &'a Grid<char>, matches: &[Match]) -> Highlighted<'a> {
+        let mut owners = Grid::new(grid.width(), grid.height(), None);
+        for (index, found) in matches.iter().enumerate() {
+            for cell in &found.cells {
+                owners[*cell] = Some(index);
+            }
+        }
+        Highlighted { grid, owners }
+    }
+
+    /// Without colors the cells outside every match are shown as `.`, with colors every letter is
+    /// kept and the matched ones take the color of their match.
+    pub fn write_text(&self, out: &mut impl Write, colors: bool) -> io::Result<()> {
+        for (row, owners) in self.grid.rows().zip(self.owners.rows()) {
+            for (letter, owner) in row.iter().zip(owners) {
+                match (owner, colors) {
+                    (Some(index), true) => {
+                        let (r, g, b) = color_of(*index);
+                        write!(out, "\x1b[1;38;2;{};{};{}m{}\x1b[0m", r, g, b, letter)?;
+                    }
+                    (None, true) => write!(out, "\x1b[2m{}\x1b[0m", letter)?,
+                    (Some(_), false) => write!(out, "{}", letter)?,
+                    (None, false) => write!(out, ".")?,
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    pub fn text(&self, colors: bool) -> String {
+        let mut out = vec![];
+        self.write_text(&mut out, colors)
+            .expect("writing to a Vec does not fail");
+        String::from_utf8(out).expect("the grid is made of chars")
+    }
+
+    /// One square per cell filled with the color of its match, letters drawn on top.
+    pub fn write_svg(&self, out: &mut impl Write) -> io::Result<()> {
+        const CELL: usize = 16;
+        let (width, height) = (self.grid.width() * CELL, self.grid.height() * CELL);
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="monospace" font-size="12" text-anchor="middle">"#,
+            width, height, width, height
+        )?;
+        let (r, g, b) = BACKGROUND;
+        writeln!(
+            out,
+            r#"<rect width="{}" height="{}" fill="rgb({},{},{})"/>"#,
+            width, height, r, g, b
+        )?;
+        for ((position, letter), owner) in self.grid.iter().zip(self.owners.values()) {
+            let (x, y) = (position.x as usize * CELL, position.y as usize * CELL);
+            let text = match owner {
+                Some(index) => {
+                    let (r, g, b) = color_of(*index);
+                    writeln!(
+                        out,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="rgb({},{},{})"/>"#,
+                        x, y, CELL, CELL, r, g, b
+                    )?;
+                    "black"
+                }
+                None => "grey",
+            };
+            let escaped = match letter {
+                '<' => "&lt;".to_string(),
+                '>' => "&gt;".to_string(),
+                '&' => "&amp;".to_string(),
+                _ => letter.to_string(),
+            };
+            writeln!(
+                out,
+                r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+                x + CELL / 2,
+                y + CELL - 4,
+                text,
+                escaped
+            )?;
+        }
+        writeln!(out, "</svg>")
+    }
+
+    /// A binary PPM where every cell is a `scale` x `scale` square, readable by most image
+    /// viewers even when the grid is far too big for a terminal.
+    pub fn write_ppm(&self, out: &mut impl Write, scale: usize) -> io::Result<()> {
+        let scale = scale.max(1);
+        write!(
+            out,
+            "P6\n{} {}\n255\n",
+            self.grid.width() * scale,
+            self.grid.height() * scale
+        )?;
+        for owners in self.owners.rows() {
+            let line = owners
+                .iter()
+                .flat_map(|owner| {
+                    let (r, g, b) = owner.map_or(UNUSED, color_of);
+                    [r, g, b].repeat(scale)
+                })
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                out.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::{
+        search::{self, SearchOptions},
+        testing::grid,
+    };
+
+    fn sample() -> Grid<char> {
+        grid("XMAS\n.<&S\n")
+    }
+
+    #[test]
+    fn test_text() {
+        let grid = sample();
+        let matches = search::find_word(&grid, "XMAS", SearchOptions::default());
+        let highlighted = Highlighted::new(&grid, &matches);
+        assert_eq!(highlighted.text(false), "XMAS\n....\n");
+
+        let colored = highlighted.text(true);
+        let (r, g, b) = color_of(0);
+        assert!(colored.starts_with(&format!("\x1b[1;38;2;{};{};{}mX\x1b[0m", r, g, b)));
+        assert!(colored.contains("\x1b[2m<\x1b[0m"));
+        assert_ne!(color_of(0), color_of(1));
+    }
+
+    #[test]
+    fn test_images() {
+        let grid = sample();
+        let matches = search::find_word(&grid, "SAMX", SearchOptions::default());
+        let highlighted = Highlighted::new(&grid, &matches);
+
+        let mut svg = vec![];
+        highlighted.write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 1 + 4);
+        assert_eq!(svg.matches("<text").count(), 8);
+        assert!(svg.contains(">&lt;</text>") && svg.contains(">&amp;</text>"));
+
+        let mut ppm = vec![];
+        highlighted.write_ppm(&mut ppm, 2).unwrap();
+        let header = b"P6\n8 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 8 * 4 * 3);
+        let (r, g, b) = UNUSED;
+        // the bottom left cell is outside the match
+        assert_eq!(ppm[ppm.len() - 8 * 3..][..3], [r, g, b]);
+    }
+}
@@ -0,0 +1,276 @@
+use rayon::prelude::*;
+
+use crate::util::{
+    geometry::{Direction8, Point},
+    grid::Grid,
+};
+
+/// Directions a word may be read in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DirectionSet {
+    Orthogonal,
+    Diagonal,
+    #[default]
+    All,
+}
+
+impl DirectionSet {
+    const ORTHOGONAL: [Direction8; 4] = [
+        Direction8::Up,
+        Direction8::Right,
+        Direction8::Down,
+        Direction8::Left,
+    ];
+
+    pub fn directions(&self) -> &'static [Direction8] {
+        match self {
+            DirectionSet::Orthogonal => &DirectionSet::ORTHOGONAL,
+            DirectionSet::Diagonal => &Direction8::DIAGONALS,
+            DirectionSet::All => &Direction8::ALL,
+        }
+    }
+
+    /// The directions a word of `length` letters is read in. A single letter reads the same in
+    /// every direction, so it only counts once.
+    pub fn for_length(&self, length: usize) -> &'static [Direction8] {
+        let directions = self.directions();
+        if length == 1 {
+            &directions[..1]
+        } else {
+            directions
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub directions: DirectionSet,
+    /// Words that run off one edge continue on the opposite one, as on a torus.
+    pub wrap_around: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub word: String,
+    pub start: Point,
+    pub direction: Direction8,
+    /// One cell per letter, from the first to the last.
+    pub cells: Vec<Point>,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// How many distinct cells a wrapping walk in `direction` visits before it is back at its start.
+pub(crate) fn period<T>(grid: &Grid<T>, direction: Direction8) -> usize {
+    let delta = direction.delta();
+    let along_x = if delta.x == 0 { 1 } else { grid.width() };
+    let along_y = if delta.y == 0 { 1 } else { grid.height() };
+    along_x / gcd(along_x, along_y) * along_y
+}
+
+fn wrap<T>(grid: &Grid<T>, position: Point) -> Point {
+    Point::new(
+        position.x.rem_euclid(grid.width() as i32),
+        position.y.rem_euclid(grid.height() as i32),
+    )
+}
+
+/// The cells of a word of `length` letters read from `start`.
+pub(crate) fn cells<T>(
+    grid: &Grid<T>,
+    start: Point,
+    direction: Direction8,
+    length: usize,
+    wrap_around: bool,
+) -> Vec<Point> {
+    (0..length as i32)
+        .map(|step| {
+            let cell = start + direction.delta() * step;
+            if wrap_around { wrap(grid, cell) } else { cell }
+        })
+        .collect()
+}
+
+/// Whether `word` can be read from `start`, comparing in place without building the path.
+fn reads_at<T: PartialEq>(
+    grid: &Grid<T>,
+    word: &[T],
+    start: Point,
+    direction: Direction8,
+    wrap_around: bool,
+) -> bool {
+    let delta = direction.delta();
+    if wrap_around {
+        // a wrapped word longer than the row or column would read a cell twice
+        if word.len() > period(grid, direction) {
+            return false;
+        }
+    } else if !grid.contains(start + delta * (word.len() as i32 - 1)) {
+        return false;
+    }
+    let mut position = start;
+    for letter in word {
+        if grid[position] != *letter {
+            return false;
+        }
+        position += delta;
+        if wrap_around {
+            position = wrap(grid, position);
+        }
+    }
+    true
+}
+
+/// Start and direction of every reading of `word` that begins on row `y`.
+fn row_hits<'a, T: PartialEq>(
+    grid: &'a Grid<T>,
+    word: &'a [T],
+    y: usize,
+    options: SearchOptions,
+) -> impl Iterator<Item = (Point, Direction8)> + 'a {
+    let directions = options.directions.for_length(word.len());
+    grid.row(y)
+        .iter()
+        .enumerate()
+        .filter(move |(_, letter)| word.first() == Some(*letter))
+        .flat_map(move |(x, _)| {
+            let start = Point::new(x as i32, y as i32);
+            directions
+                .iter()
+                .filter(move |direction| {
+                    reads_at(grid, word, start, **direction, options.wrap_around)
+                })
+                .map(move |direction| (start, *direction))
+        })
+}
+
+/// Every reading of `word`, row by row, found lazily and without allocating.
+pub fn hits<'a, T: PartialEq>(
+    grid: &'a Grid<T>,
+    word: &'a [T],
+    options: SearchOptions,
+) -> impl Iterator<Item = (Point, Direction8)> + 'a {
+    let height = if word.is_empty() { 0 } else { grid.height() };
+    (0..height).flat_map(move |y| row_hits(grid, word, y, options))
+}
+
+/// Counts the readings of `word` with the rows split across rayon workers.
+pub fn par_count<T: PartialEq + Sync>(grid: &Grid<T>, word: &[T], options: SearchOptions) -> usize {
+    if word.is_empty() {
+        return 0;
+    }
+    (0..grid.height())
+        .into_par_iter()
+        .map(|y| row_hits(grid, word, y, options).count())
+        .sum()
+}
+
+/// Every place where one of `words` can be read, each direction counting as its own match.
+pub fn find_words(grid: &Grid<char>, words: &[&str], options: SearchOptions) -> Vec<Match> {
+    let mut matches = vec![];
+    for word in words {
+        let letters = word.chars().collect::<Vec<_>>();
+        matches.extend(
+            hits(grid, &letters, options).map(|(start, direction)| Match {
+                word: word.to_string(),
+                start,
+                direction,
+                cells: cells(grid, start, direction, letters.len(), options.wrap_around),
+            }),
+        );
+    }
+    matches
+}
+
+pub fn find_word(grid: &Grid<char>, word: &str, options: SearchOptions) -> Vec<Match> {
+    find_words(grid, &[word], options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::testing::grid;
+
+    #[test]
+    fn test_direction_sets() {
+        let grid = grid("CAT\nAA.\nT.T\n");
+        let count = |directions| {
+            find_word(
+                &grid,
+                "CAT",
+                SearchOptions {
+                    directions,
+                    wrap_around: false,
+                },
+            )
+            .len()
+        };
+        assert_eq!(count(DirectionSet::Orthogonal), 2);
+        assert_eq!(count(DirectionSet::Diagonal), 1);
+        assert_eq!(count(DirectionSet::All), 3);
+
+        let diagonal = find_word(
+            &grid,
+            "CAT",
+            SearchOptions {
+                directions: DirectionSet::Diagonal,
+                wrap_around: false,
+            },
+        );
+        assert_eq!(
+            diagonal,
+            vec![Match {
+                word: "CAT".to_string(),
+                start: Point::new(0, 0),
+                direction: Direction8::DownRight,
+                cells: vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_wrap_around_and_word_lists() {
+        let grid = grid("TDOG\n....\n");
+        let plain = SearchOptions::default();
+        let wrapping = SearchOptions {
+            wrap_around: true,
+            ..plain
+        };
+        assert!(find_word(&grid, "DOGT", plain).is_empty());
+        let wrapped = find_word(&grid, "DOGT", wrapping);
+        assert_eq!(wrapped.len(), 1);
+        assert_eq!(wrapped[0].cells.last(), Some(&Point::new(0, 0)));
+        // longer than the row, the word would have to reuse a letter
+        assert!(find_word(&grid, "DOGTD", wrapping).is_empty());
+
+        let matches = find_words(&grid, &["DOG", "GOD", "T"], plain);
+        let words = matches
+            .iter()
+            .map(|found| found.word.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(words, vec!["DOG", "GOD", "T"]);
+    }
+
+    #[test]
+    fn test_byte_hits_and_parallel_count() {
+        let text = "XMASX\nSAMXM\nAMSAA\nXMASS\n";
+        let chars = grid(text);
+        let bytes = chars.map(|_, letter| *letter as u8);
+        for wrap_around in [false, true] {
+            let options = SearchOptions {
+                wrap_around,
+                ..SearchOptions::default()
+            };
+            let expected = find_word(&chars, "XMAS", options)
+                .into_iter()
+                .map(|found| (found.start, found.direction))
+                .collect::<Vec<_>>();
+            let found = hits(&bytes, b"XMAS", options).collect::<Vec<_>>();
+            assert_eq!(found, expected);
+            assert_eq!(par_count(&bytes, b"XMAS", options), expected.len());
+        }
+        assert_eq!(hits(&bytes, b"", SearchOptions::default()).count(), 0);
+    }
+}
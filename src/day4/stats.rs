@@ -0,0 +1,131 @@
+use crate::{
+    day4::search::Match,
+    util::{
+        geometry::{Direction8, Point},
+        grid::Grid,
+    },
+};
+
+/// How a set of matches covers the grid they were found in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchStats {
+    pub matches: usize,
+    /// How many matches use each cell.
+    pub usage: Grid<usize>,
+    /// Directions with at least one match, the most common first.
+    pub directions: Vec<(Direction8, usize)>,
+}
+
+impl MatchStats {
+    pub fn of<T>(grid: &Grid<T>, matches: &[Match]) -> MatchStats {
+        let mut usage = Grid::new(grid.width(), grid.height(), 0);
+        let mut per_direction = [0; 8];
+        for found in matches {
+            per_direction[found.direction as usize] += 1;
+            for cell in &found.cells {
+                usage[*cell] += 1;
+            }
+        }
+        let mut directions = Direction8::ALL
+            .into_iter()
+            .zip(per_direction)
+            .filter(|(_, count)| *count > 0)
+            .collect::<Vec<_>>();
+        // stable, so ties keep the clockwise order
+        directions.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        MatchStats {
+            matches: matches.len(),
+            usage,
+            directions,
+        }
+    }
+
+    /// Cells that are part of at least one match.
+    pub fn used_cells(&self) -> usize {
+        self.usage.values().filter(|count| **count > 0).count()
+    }
+
+    /// Cells that more than one match goes through.
+    pub fn shared_cells(&self) -> usize {
+        self.usage.values().filter(|count| **count > 1).count()
+    }
+
+    /// The most used cell, the first one row by row on ties.
+    pub fn busiest_cell(&self) -> Option<(Point, usize)> {
+        self.usage
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .fold(None, |best, (position, count)| match best {
+                Some((_, most)) if most >= *count => best,
+                _ => Some((position, *count)),
+            })
+    }
+
+    /// The `size` x `size` square with the most cell uses, by its top left corner.
+    pub fn densest_region(&self, size: usize) -> Option<(Point, usize)> {
+        let (width, height) = (self.usage.width(), self.usage.height());
+        if size == 0 || size > width || size > height {
+            return None;
+        }
+        // sums[y][x] holds the uses above and to the left of (x, y)
+        let mut sums = vec![vec![0; width + 1]; height + 1];
+        for y in 0..height {
+            for x in 0..width {
+                sums[y + 1][x + 1] =
+                    self.usage.row(y)[x] + sums[y][x + 1] + sums[y + 1][x] - sums[y][x];
+            }
+        }
+        let mut best: Option<(Point, usize)> = None;
+        for y in 0..=height - size {
+            for x in 0..=width - size {
+                let total =
+                    sums[y + size][x + size] + sums[y][x] - sums[y][x + size] - sums[y + size][x];
+                if best.is_none_or(|(_, most)| total > most) {
+                    best = Some((Point::new(x as i32, y as i32), total));
+                }
+            }
+        }
+        best
+    }
+
+    /// One char per cell: `.` for unused cells, the number of matches up to 9 and `+` above.
+    pub fn heat_map(&self) -> Grid<char> {
+        self.usage.map(|_, count| match count {
+            0 => '.',
+            1..=9 => char::from_digit(*count as u32, 10).unwrap(),
+            _ => '+',
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::{
+        search::{self, SearchOptions},
+        testing::grid,
+    };
+
+    #[test]
+    fn test_match_stats() {
+        let grid = grid("CAT.\nAA..\nT.T.\n");
+        let matches = search::find_word(&grid, "CAT", SearchOptions::default());
+        let stats = MatchStats::of(&grid, &matches);
+
+        assert_eq!(stats.matches, 3);
+        assert_eq!(stats.heat_map().to_string(), "311.\n11..\n1.1.\n");
+        assert_eq!(stats.used_cells(), 7);
+        assert_eq!(stats.shared_cells(), 1);
+        assert_eq!(stats.busiest_cell(), Some((Point::new(0, 0), 3)));
+        assert_eq!(
+            stats.directions,
+            vec![
+                (Direction8::Right, 1),
+                (Direction8::DownRight, 1),
+                (Direction8::Down, 1)
+            ]
+        );
+        assert_eq!(stats.densest_region(2), Some((Point::new(0, 0), 6)));
+        assert_eq!(stats.densest_region(5), None);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::util::{
+    geometry::Point,
+    grid::{Grid, GridError},
+};
+
+/// Template cells holding this char match any letter.
+pub const WILDCARD: char = '.';
+
+/// Which copies of a template are searched for besides the template itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Symmetry {
+    #[default]
+    Fixed,
+    /// The four quarter turns.
+    Rotations,
+    /// The quarter turns of the template and of its mirror image.
+    RotationsAndReflections,
+}
+
+/// A small 2D shape of letters, with wildcards left out of `cells`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stencil {
+    cells: Vec<(Point, char)>,
+}
+
+/// A place where a variant of the stencil matches. `origin` is the top left corner of the
+/// variant's letters and `cells` lists the matched letters row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placement {
+    pub origin: Point,
+    pub variant: usize,
+    pub cells: Vec<Point>,
+}
+
+impl Stencil {
+    /// One line per row, like the grid it is matched against, with [`WILDCARD`] for any letter.
+    pub fn parse(template: &str) -> Result<Stencil, GridError> {
+        let grid = Grid::parse(template, |c| c)?;
+        Ok(Stencil::normalized(
+            grid.iter()
+                .filter(|(_, letter)| **letter != WILDCARD)
+                .map(|(position, letter)| (position, *letter))
+                .collect(),
+        ))
+    }
+
+    /// Moves the shape so its top left corner is the origin, with the cells row by row.
+    fn normalized(mut cells: Vec<(Point, char)>) -> Stencil {
+        let min_x = cells
+            .iter()
+            .map(|(position, _)| position.x)
+            .min()
+            .unwrap_or(0);
+        let min_y = cells
+            .iter()
+            .map(|(position, _)| position.y)
+            .min()
+            .unwrap_or(0);
+        for (position, _) in cells.iter_mut() {
+            *position -= Point::new(min_x, min_y);
+        }
+        cells.sort_by_key(|(position, _)| (position.y, position.x));
+        Stencil { cells }
+    }
+
+    fn transformed(&self, transform: impl Fn(Point) -> Point) -> Stencil {
+        Stencil::normalized(
+            self.cells
+                .iter()
+                .map(|(position, letter)| (transform(*position), *letter))
+                .collect(),
+        )
+    }
+
+    /// The distinct copies of the stencil allowed by `symmetry`, the stencil itself first.
+    pub fn variants(&self, symmetry: Symmetry) -> Vec<Stencil> {
+        let mut bases = vec![self.clone()];
+        if symmetry == Symmetry::RotationsAndReflections {
+            bases.push(self.transformed(|position| Point::new(-position.x, position.y)));
+        }
+        let turns = if symmetry == Symmetry::Fixed { 1 } else { 4 };
+        let mut variants: Vec<Stencil> = vec![];
+        for base in bases {
+            let mut variant = base;
+            for _ in 0..turns {
+                let next = variant.transformed(|position| position.rotate_right());
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+                variant = next;
+            }
+        }
+        variants
+    }
+
+    fn matches_at<T: Copy + Into<char>>(&self, grid: &Grid<T>, origin: Point) -> bool {
+        self.cells.iter().all(|(position, letter)| {
+            grid.get(origin + *position)
+                .is_some_and(|cell| (*cell).into() == *letter)
+        })
+    }
+}
+
+/// Every placement of every variant of `stencil` inside a grid of chars or ASCII bytes.
+pub fn find_stencil<T: Copy + Into<char>>(
+    grid: &Grid<T>,
+    stencil: &Stencil,
+    symmetry: Symmetry,
+) -> Vec<Placement> {
+    let variants = stencil.variants(symmetry);
+    let mut placements = vec![];
+    for origin in grid.positions() {
+        for (index, variant) in variants.iter().enumerate() {
+            if variant.matches_at(grid, origin) {
+                placements.push(Placement {
+                    origin,
+                    variant: index,
+                    cells: variant
+                        .cells
+                        .iter()
+                        .map(|(position, _)| origin + *position)
+                        .collect(),
+                });
+            }
+        }
+    }
+    placements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::testing::grid;
+
+    #[test]
+    fn test_variants() {
+        let cross = Stencil::parse("M.S\n.A.\nM.S\n").unwrap();
+        assert_eq!(cross.variants(Symmetry::Fixed).len(), 1);
+        assert_eq!(cross.variants(Symmetry::Rotations).len(), 4);
+        // the mirror image of the cross is one of its rotations
+        assert_eq!(cross.variants(Symmetry::RotationsAndReflections).len(), 4);
+
+        let corner = Stencil::parse("AB\nC.\n").unwrap();
+        assert_eq!(corner.variants(Symmetry::RotationsAndReflections).len(), 8);
+        let plus = Stencil::parse(".X.\nXXX\n.X.\n").unwrap();
+        assert_eq!(plus.variants(Symmetry::RotationsAndReflections).len(), 1);
+    }
+
+    #[test]
+    fn test_find_stencil() {
+        let grid = grid("SXS\nXAX\nMXM\nXAX\nSXS\n");
+        let cross = Stencil::parse("M.S\n.A.\nM.S\n").unwrap();
+        assert!(find_stencil(&grid, &cross, Symmetry::Fixed).is_empty());
+        let found = find_stencil(&grid, &cross, Symmetry::Rotations);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].origin, Point::new(0, 0));
+        assert_eq!(found[0].cells[2], Point::new(1, 1));
+        assert_eq!(found[1].origin, Point::new(0, 2));
+
+        let plus = Stencil::parse(".X.\nXAX\n.X.\n").unwrap();
+        let found = find_stencil(&grid, &plus, Symmetry::Fixed);
+        assert_eq!(
+            found.iter().map(|found| found.origin).collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(0, 2)]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+};
+
+use crate::{
+    day4::search::DirectionSet,
+    util::{
+        geometry::{Direction8, Point},
+        grid::GridError,
+    },
+};
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Grid(GridError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(error) => write!(f, "could not read the grid: {}", error),
+            StreamError::Grid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Io(error) => Some(error),
+            StreamError::Grid(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        StreamError::Io(error)
+    }
+}
+
+impl From<GridError> for StreamError {
+    fn from(error: GridError) -> Self {
+        StreamError::Grid(error)
+    }
+}
+
+/// Readings of a word in a grid read line by line, holding only `word.len()` rows at a time.
+/// Every reading is reported once the row it reaches highest is at the top of the window, so
+/// the memory used does not depend on the height of the grid.
+pub struct StreamHits<R> {
+    reader: R,
+    word: Vec<u8>,
+    directions: &'static [Direction8],
+    window: VecDeque<Vec<u8>>,
+    /// Row number of the first row in the window.
+    top: usize,
+    width: Option<usize>,
+    found: VecDeque<(Point, Direction8)>,
+    finished: bool,
+}
+
+impl<R: BufRead> StreamHits<R> {
+    pub fn new(reader: R, word: &[u8], directions: DirectionSet) -> StreamHits<R> {
+        StreamHits {
+            reader,
+            word: word.to_vec(),
+            directions: directions.for_length(word.len()),
+            window: VecDeque::with_capacity(word.len()),
+            top: 0,
+            width: None,
+            found: VecDeque::new(),
+            finished: word.is_empty(),
+        }
+    }
+
+    /// Reads rows until the window is full or the input ends, reusing the row that just left.
+    fn fill(&mut self, mut spare: Vec<u8>) -> Result<(), StreamError> {
+        while self.window.len() < self.word.len() {
+            spare.clear();
+            if self.reader.read_until(b'\n', &mut spare)? == 0 {
+                break;
+            }
+            if spare.last() == Some(&b'\n') {
+                spare.pop();
+                if spare.last() == Some(&b'\r') {
+                    spare.pop();
+                }
+            }
+            let line = self.top + self.window.len() + 1;
+            match self.width {
+                None if spare.is_empty() => return Err(GridError::Empty.into()),
+                None => self.width = Some(spare.len()),
+                Some(expected) if expected != spare.len() => {
+                    return Err(GridError::Ragged {
+                        line,
+                        expected,
+                        found: spare.len(),
+                    }
+                    .into());
+                }
+                Some(_) => {}
+            }
+            self.window.push_back(std::mem::take(&mut spare));
+        }
+        Ok(())
+    }
+
+    /// Queues the readings whose highest row is the top of the window.
+    fn scan_top(&mut self) {
+        let length = self.word.len();
+        let width = self.width.unwrap_or(0) as i32;
+        for direction in self.directions {
+            let delta = direction.delta();
+            // readings going up start at the bottom of the window and end at its top
+            let first_row = if delta.y < 0 { length - 1 } else { 0 };
+            let Some(row) = self.window.get(first_row) else {
+                continue;
+            };
+            for (x, letter) in row.iter().enumerate() {
+                if *letter != self.word[0] {
+                    continue;
+                }
+                let start = Point::new(x as i32, first_row as i32);
+                let reads = self.word.iter().enumerate().skip(1).all(|(step, letter)| {
+                    let cell = start + delta * step as i32;
+                    cell.x >= 0
+                        && cell.x < width
+                        && self
+                            .window
+                            .get(cell.y as usize)
+                            .is_some_and(|row| row[cell.x as usize] == *letter)
+                });
+                if reads {
+                    let start = Point::new(start.x, (self.top + first_row) as i32);
+                    self.found.push_back((start, *direction));
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for StreamHits<R> {
+    type Item = Result<(Point, Direction8), StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.found.pop_front() {
+                return Some(Ok(found));
+            }
+            if self.finished {
+                return None;
+            }
+            let spare = if self.window.len() == self.word.len() {
+                self.top += 1;
+                self.window.pop_front().unwrap_or_default()
+            } else {
+                vec![]
+            };
+            if let Err(error) = self.fill(spare) {
+                self.finished = true;
+                return Some(Err(error));
+            }
+            if self.window.is_empty() {
+                self.finished = true;
+                if self.width.is_none() {
+                    return Some(Err(GridError::Empty.into()));
+                }
+                return None;
+            }
+            self.scan_top();
+            // a short window means the input ended, the next pass only drops its top row
+            if self.window.len() < self.word.len() {
+                self.window.pop_front();
+                self.top += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::{
+        search::{self, SearchOptions},
+        testing::random_grid,
+    };
+
+    #[test]
+    fn test_stream_agrees_with_search() {
+        let grid = random_grid(25, 30, b"XMAS", 11).map(|_, letter| *letter as u8);
+        let text = grid
+            .rows()
+            .map(|row| String::from_utf8_lossy(row) + "\r\n")
+            .collect::<String>();
+        for word in [&b"XMAS"[..], b"SAX", b"M"] {
+            for directions in [
+                DirectionSet::Orthogonal,
+                DirectionSet::Diagonal,
+                DirectionSet::All,
+            ] {
+                let options = SearchOptions {
+                    directions,
+                    wrap_around: false,
+                };
+                let mut expected = search::hits(&grid, word, options).collect::<Vec<_>>();
+                let mut streamed = StreamHits::new(text.as_bytes(), word, directions)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                let key = |(start, direction): &(Point, Direction8)| (*start, *direction as usize);
+                expected.sort_by_key(key);
+                streamed.sort_by_key(key);
+                assert_eq!(streamed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_errors() {
+        let ragged = StreamHits::new("XMAS\nXM\nXMAS\n".as_bytes(), b"XMAS", DirectionSet::All)
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            ragged.last(),
+            Some(Err(StreamError::Grid(GridError::Ragged {
+                line: 2,
+                expected: 4,
+                found: 2
+            })))
+        ));
+        let mut empty = StreamHits::new("".as_bytes(), b"XMAS", DirectionSet::All);
+        assert!(matches!(
+            empty.next(),
+            Some(Err(StreamError::Grid(GridError::Empty)))
+        ));
+        assert!(empty.next().is_none());
+    }
+}
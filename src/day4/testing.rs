@@ -0,0 +1,13 @@
+use crate::util::grid::Grid;
+
+pub fn grid(text: &str) -> Grid<char> {
+    Grid::parse(text, |c| c).unwrap()
+}
+
+/// A deterministic pseudo random grid over `letters`, a small alphabet makes words repeat a lot.
+pub fn random_grid(width: usize, height: usize, letters: &[u8], mut seed: u32) -> Grid<char> {
+    Grid::from_fn(width, height, |_| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        letters[(seed >> 16) as usize % letters.len()] as char
+    })
+}
@@ -0,0 +1,201 @@
+use std::collections::{HashMap, HashSet, VecDeque, hash_map::Entry};
+
+use crate::day5::{
+    day5::{self, FixError, Page},
+    graph::PrecedenceGraph,
+};
+
+/// What is wrong with a set of rules, if anything.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Analysis {
+    /// One cycle per group of pages the rules order in a circle.
+    pub cycles: Vec<Vec<u32>>,
+    pub contradictions: Vec<(u32, u32)>,
+    /// One entry per update, an error when no order of its pages follows every rule.
+    pub orderings: Vec<Result<(), FixError>>,
+}
+
+pub fn analyze(page: &Page) -> Analysis {
+    let graph = PrecedenceGraph::new(&page.rules);
+    Analysis {
+        cycles: cycles(&graph),
+        contradictions: contradictions(&graph),
+        orderings: page
+            .updates
+            .iter()
+            .map(|update| day5::fix(&graph, update).map(|_| ()))
+            .collect(),
+    }
+}
+
+/// Pairs of pages with a rule each way round, the smaller page first.
+pub fn contradictions(graph: &PrecedenceGraph) -> Vec<(u32, u32)> {
+    let mut pairs = graph
+        .rules()
+        .filter(|rule| rule.before < rule.after && graph.must_precede(rule.after, rule.before))
+        .map(|rule| (rule.before, rule.after))
+        .collect::<Vec<_>>();
+    pairs.sort();
+    pairs
+}
+
+/// Strongly connected components, found with Kosaraju's algorithm without recursion so long
+/// chains of rules cannot overflow the stack.
+fn components(graph: &PrecedenceGraph) -> Vec<HashSet<u32>> {
+    let mut pages = graph.pages().into_iter().collect::<Vec<_>>();
+    pages.sort();
+
+    let mut visited = HashSet::new();
+    let mut finished = vec![];
+    for root in &pages {
+        if !visited.insert(*root) {
+            continue;
+        }
+        let mut stack = vec![(*root, graph.successors(*root).collect::<Vec<_>>())];
+        while let Some((page, pending)) = stack.last_mut() {
+            if let Some(next) = pending.pop() {
+                if visited.insert(next) {
+                    stack.push((next, graph.successors(next).collect()));
+                }
+            } else {
+                finished.push(*page);
+                stack.pop();
+            }
+        }
+    }
+
+    let mut predecessors: HashMap<u32, Vec<u32>> = HashMap::new();
+    for rule in graph.rules() {
+        predecessors
+            .entry(rule.after)
+            .or_default()
+            .push(rule.before);
+    }
+    let mut assigned = HashSet::new();
+    let mut components = vec![];
+    for root in finished.iter().rev() {
+        if !assigned.insert(*root) {
+            continue;
+        }
+        let mut component = HashSet::from([*root]);
+        let mut stack = vec![*root];
+        while let Some(page) = stack.pop() {
+            for before in predecessors.get(&page).into_iter().flatten() {
+                if assigned.insert(*before) {
+                    component.insert(*before);
+                    stack.push(*before);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// The shortest cycle through the smallest page of `component`, in rule order.
+fn shortest_cycle(graph: &PrecedenceGraph, component: &HashSet<u32>) -> Option<Vec<u32>> {
+    let start = *component.iter().min()?;
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(page) = queue.pop_front() {
+        let mut successors = graph
+            .successors(page)
+            .filter(|after| component.contains(after))
+            .collect::<Vec<_>>();
+        successors.sort();
+        for after in successors {
+            if after == start {
+                let mut cycle = vec![page];
+                let mut current = page;
+                while current != start {
+                    current = parents[&current];
+                    cycle.push(current);
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if let Entry::Vacant(entry) = parents.entry(after) {
+                entry.insert(page);
+                queue.push_back(after);
+            }
+        }
+    }
+    None
+}
+
+/// Cycles of rules, one per group of pages that all have to come before each other, sorted by
+/// their first page. A page that has to precede itself is a cycle of one.
+pub fn cycles(graph: &PrecedenceGraph) -> Vec<Vec<u32>> {
+    let mut cycles = components(graph)
+        .iter()
+        .filter_map(|component| shortest_cycle(graph, component))
+        .collect::<Vec<_>>();
+    cycles.sort();
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day5::day5::{Rule, Update};
+
+    fn page(rules: &[(u32, u32)], updates: &[&[u32]]) -> Page {
+        Page {
+            rules: rules
+                .iter()
+                .map(|(before, after)| Rule {
+                    before: *before,
+                    after: *after,
+                })
+                .collect(),
+            updates: updates.iter().map(|pages| Update(pages.to_vec())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_analyze() {
+        let rules = [
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (3, 4),
+            (4, 5),
+            (5, 4),
+            (6, 6),
+            (7, 8),
+        ];
+        let analysis = analyze(&page(&rules, &[&[1, 2, 3], &[3, 1, 4], &[8, 7], &[6]]));
+        assert_eq!(analysis.cycles, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+        assert_eq!(analysis.contradictions, vec![(4, 5)]);
+        assert_eq!(
+            analysis.orderings,
+            vec![
+                Err(FixError::Cycle {
+                    pages: vec![1, 2, 3]
+                }),
+                Ok(()),
+                Ok(()),
+                Err(FixError::Cycle { pages: vec![6] }),
+            ]
+        );
+
+        let clean = analyze(&page(&[(1, 2), (2, 3), (1, 3)], &[&[3, 2, 1]]));
+        assert_eq!(
+            clean,
+            Analysis {
+                orderings: vec![Ok(())],
+                ..Analysis::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_long_chain_does_not_overflow() {
+        let mut rules = (0..20_000).map(|page| (page, page + 1)).collect::<Vec<_>>();
+        rules.push((20_000, 0));
+        let graph = PrecedenceGraph::new(&page(&rules, &[]).rules);
+        let cycles = cycles(&graph);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 20_001);
+    }
+}
@@ -1,518 +1,430 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt,
+};
 
-use crate::util::util;
+use crate::{
+    day5::{analysis, graph::PrecedenceGraph},
+    solution::solution::{Answer, Solution, SolutionError},
+};
 
-fn extract_rules_and_rows_from_input(input: &str) -> Page {
-    let lines = util::read_input("day5", input);
-    lines.lines().fold(Page::new(), |mut current_page, line| {
-        let is_rule = line.contains('|');
-        let is_row = line.contains(',');
-        if is_rule {
-            current_page.raw_rules.push(line.to_string());
-        }
-        if is_row {
-            current_page.raw_lines.push(line.to_string());
-        }
-        return current_page;
-    })
+/// `before|after`: when an update has both pages, `before` has to come first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub before: u32,
+    pub after: u32,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}|{}", self.before, self.after)
+    }
 }
-#[derive(Clone)]
-struct Page {
-    raw_rules: Vec<String>,
-    raw_lines: Vec<String>,
+
+/// The pages of one update, in the order they are printed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Update(pub Vec<u32>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page {
+    pub rules: Vec<Rule>,
+    pub updates: Vec<Update>,
 }
 
-impl Page {
-    fn new() -> Page {
-        return Page {
-            raw_lines: vec![],
-            raw_rules: vec![],
-        };
+/// Line numbers start at 1, like an editor shows them.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    MalformedRule { line: usize, text: String },
+    NotANumber { line: usize, text: String },
+    RuleAfterUpdates { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MalformedRule { line, text } => write!(
+                f,
+                "line {}: rule {:?} should be two pages separated by |",
+                line, text
+            ),
+            ParseError::NotANumber { line, text } => {
+                write!(f, "line {}: {:?} is not a page number", line, text)
+            }
+            ParseError::RuleAfterUpdates { line } => {
+                write!(f, "line {}: rules have to come before the updates", line)
+            }
+        }
     }
 }
 
-struct RuleTrie {
-    rule_trie: Vec<TrieNode>,
-    rule_map_trie: HashMap<(String, String), TrieNode>,
-    page: Page,
+impl Error for ParseError {}
+
+fn page_number(text: &str, line: usize) -> Result<u32, ParseError> {
+    text.trim().parse().map_err(|_| ParseError::NotANumber {
+        line,
+        text: text.to_string(),
+    })
 }
 
-impl RuleTrie {
-    fn new(page: &Page) -> RuleTrie {
-        let rule_trie =
-            page.raw_rules
-                .iter()
-                .fold(vec![], |mut map: Vec<TrieNode>, rule: &String| {
-                    println!("rule inserted: {}", rule);
-                    let trie_node = TrieNode::new(rule);
-                    // trie_node.print(0);
-                    map.push(trie_node);
-                    map
+/// Rules come first, one `a|b` per line, then the updates as comma separated pages. Blank lines
+/// are skipped.
+pub fn parse_page(input: &str) -> Result<Page, ParseError> {
+    let mut page = Page {
+        rules: vec![],
+        updates: vec![],
+    };
+    for (index, text) in input.lines().enumerate() {
+        let line = index + 1;
+        if text.trim().is_empty() {
+            continue;
+        }
+        if text.contains('|') {
+            if !page.updates.is_empty() {
+                return Err(ParseError::RuleAfterUpdates { line });
+            }
+            let Some((before, after)) = text
+                .split_once('|')
+                .filter(|(_, after)| !after.contains('|'))
+            else {
+                return Err(ParseError::MalformedRule {
+                    line,
+                    text: text.to_string(),
                 });
-        let rule_map_trie = page
-            .raw_rules
-            .iter()
-            .fold(HashMap::new(), |mut map, rule: &String| {
-                let split_rules = rule.split("|").collect::<Vec<&str>>();
-                let first = split_rules.first().unwrap();
-                let second = split_rules.get(1).unwrap();
-                map.insert(
-                    (first.to_owned().to_owned(), second.to_owned().to_owned()),
-                    TrieNode::new(rule),
-                );
-                map
+            };
+            page.rules.push(Rule {
+                before: page_number(before, line)?,
+                after: page_number(after, line)?,
             });
-        RuleTrie {
-            rule_trie,
-            rule_map_trie,
-            page: page.clone(),
+        } else {
+            let pages = text
+                .split(',')
+                .map(|page| page_number(page, line))
+                .collect::<Result<Vec<_>, _>>()?;
+            page.updates.push(Update(pages));
         }
     }
-    fn find_rules_map(&self, combination: (String, String)) -> Option<&TrieNode> {
-        self.rule_map_trie.get(&combination)
+    Ok(page)
+}
+
+/// A rule an update breaks: `rule.after` is printed at `after_position`, before `rule.before`
+/// at `before_position`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: Rule,
+    pub before_position: usize,
+    pub after_position: usize,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is broken, page {} is at position {} and page {} at position {}",
+            self.rule, self.rule.after, self.after_position, self.rule.before, self.before_position
+        )
     }
-    fn find_rules(&self, rule: String) -> Option<String> {
-        let is_equal = |new_rule: &str| &rule == new_rule;
-        let found_rule = self.rule_trie.iter().find(|node| {
-            //node.print(0, 0);
-            match &node.value {
-                Some(v) => is_equal(&v),
-                None => {
-                    //TODO: FIX only get rules that match
-                    let rule_option = node.nodes.get(&rule);
-                    match rule_option {
-                        Some(_) => true,
-                        None => false,
-                    }
-                }
-            }
-        });
-        match found_rule {
-            Some(found_rule) => (found_rule).get_value_list().first().cloned(),
-            None => None,
+}
+
+/// Checks the update in one pass, looking back from every page at the pages it has to precede.
+/// The first violation is the one found at the earliest page, then at the earliest page before
+/// it.
+pub fn validate(graph: &PrecedenceGraph, update: &Update) -> Result<(), Violation> {
+    let mut positions = HashMap::with_capacity(update.0.len());
+    for (position, page) in update.0.iter().enumerate() {
+        let earliest = graph
+            .successors(*page)
+            .filter_map(|after| positions.get(&after).map(|seen| (*seen, after)))
+            .min();
+        if let Some((after_position, after)) = earliest {
+            return Err(Violation {
+                rule: Rule {
+                    before: *page,
+                    after,
+                },
+                before_position: position,
+                after_position,
+            });
         }
+        positions.entry(*page).or_insert(position);
     }
-    fn find_rule_from_pair(&self, (first_rule, second_rule): (String, String)) -> Option<String> {
-        self.rule_trie
-            .iter()
-            .find_map(|node| match node.nodes.get(&first_rule) {
-                Some(first_trie) => {
-                    // first_trie.print(0, 3);
-                    match first_trie.nodes.get(&second_rule) {
-                        Some(second_trie) => second_trie.value.clone(),
-                        None => None,
-                    }
-                }
-                None => None,
-            })
-    }
+    Ok(())
+}
 
-    fn get_lines_that_comply_rules(&self) -> Vec<(String, Vec<Option<&TrieNode>>)> {
-        let lines_that_comply_rules = self
-            .page
-            .raw_lines
-            .iter()
-            .filter_map(|line| {
-                println!("checking line: {}", line);
-                let combinations =
-                    create_combinations(vec![], line.split(",").collect::<Vec<&str>>(), 2);
-                println!("combinations: {:?}", combinations);
-                let mut rules = combinations
-                    .into_iter()
-                    .map(|pair| {
-                        //rule_trie.find_rule_from_pair((pair.0.to_owned(), pair.1.to_owned()))
-                        self.find_rules_map((pair.0.to_owned(), pair.1.to_owned()))
-                    })
-                    .collect::<Vec<_>>();
-                rules.retain(|rule| rule.is_some());
-                println!("rules that match: {:?}", rules.len());
-
-                if rules.iter().len() == 0 {
-                    return None;
-                }
-                let comply = match {
-                    rules.iter().all(|rule| {
-                        let binding = rule.unwrap().get_value_list();
-                        let rule = binding.first().unwrap();
-                        println!("match line {} with rule {}", line, &rule);
-                        match is_rule_valid_on_line(line, rule.as_str()) {
-                            Some(true) => true,
-                            _ => false,
-                        }
-                    })
-                } {
-                    true => Some((line.to_string(), rules.to_owned())),
-                    false => None,
-                };
-                comply
-            })
-            .collect::<Vec<_>>();
-        lines_that_comply_rules.iter().for_each(|line| {
-            println!("line that complies: {}", line.0.as_str());
-        });
-        lines_that_comply_rules
-    }
-    fn get_lines_that_dont_comply_rules(&self) -> Vec<(String, Vec<Option<&TrieNode>>)> {
-        let lines_that_comply_rules = self
-            .page
-            .raw_lines
-            .iter()
-            .filter_map(|line| {
-                println!("checking line: {}", line);
-                let combinations =
-                    create_combinations(vec![], line.split(",").collect::<Vec<&str>>(), 2);
-                println!("combinations: {:?}", combinations);
-                let mut rules = combinations
-                    .into_iter()
-                    .map(|pair| {
-                        //rule_trie.find_rule_from_pair((pair.0.to_owned(), pair.1.to_owned()))
-                        self.find_rules_map((pair.0.to_owned(), pair.1.to_owned()))
-                    })
-                    .collect::<Vec<_>>();
-                rules.retain(|rule| rule.is_some());
-                println!("rules that match: {:?}", rules.len());
-
-                if rules.iter().len() == 0 {
-                    return None;
-                }
-                let comply = match {
-                    rules.iter().any(|rule| {
-                        let binding = rule.unwrap().get_value_list();
-                        let rule = binding.first().unwrap();
-                        println!("match line {} with rule {}", line, &rule);
-                        !match is_rule_valid_on_line(line, rule.as_str()) {
-                            Some(true) => true,
-                            _ => false,
-                        }
-                    })
-                } {
-                    true => Some((line.to_string(), rules.to_owned())),
-                    false => None,
-                };
-                comply
-            })
-            .collect::<Vec<_>>();
-        lines_that_comply_rules.iter().for_each(|line| {
-            println!("line that complies: {}", line.0.as_str());
-        });
-        lines_that_comply_rules
-    }
+fn get_lines_that_comply_rules<'a>(graph: &PrecedenceGraph, page: &'a Page) -> Vec<&'a Update> {
+    page.updates
+        .iter()
+        .filter(|line| validate(graph, line).is_ok())
+        .collect()
 }
 
-fn create_combinations<T: Clone>(mut acc: Vec<(T, T)>, vec: Vec<T>, size: u8) -> Vec<(T, T)> {
-    if vec.len() == 1 {
-        return acc;
-    }
-    let first = vec.first().unwrap();
-    let remainder = &vec[1..];
-    let mut combinations = remainder
+fn get_lines_that_dont_comply_rules<'a>(
+    graph: &PrecedenceGraph,
+    page: &'a Page,
+) -> Vec<&'a Update> {
+    page.updates
         .iter()
-        .flat_map(|curr| vec![(first.clone(), curr.clone()), (curr.clone(), first.clone())])
-        .collect::<Vec<_>>();
-    acc.append(&mut combinations);
-    create_combinations(acc, remainder.to_vec(), size)
+        .filter(|line| validate(graph, line).is_err())
+        .collect()
 }
 
-#[derive(Clone)]
-struct TrieNode {
-    nodes: HashMap<String, TrieNode>,
-    value: Option<String>,
+fn get_midpoints_of_lines(lines: &[Update]) -> Vec<u32> {
+    lines.iter().map(|curr| curr.0[curr.0.len() / 2]).collect()
 }
 
-impl TrieNode {
-    fn new(rule: &str) -> TrieNode {
-        cyclical_tree_build(rule, rule.split("|").collect())
-    }
-    fn print(&self, level: u8, depth: u8) {
-        if level > depth {
-            return;
-        }
-        println!(
-            "-----------------LEVEL: {}--------------------------------------------",
-            level
-        );
-        println!("value: {}", self.value.clone().unwrap_or("None".to_owned()));
-        println!("nodes: {:?}", self.nodes.clone().into_keys());
-        self.nodes.clone().into_iter().for_each(|(key, node)| {
-            println!(
-                "-----------------KEY: {}--------------------------------------------",
-                key
-            );
-            node.print(level + 1, depth);
-        });
-    }
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FixError {
+    /// The rules between the pages of the update go round in a circle, `pages` lists one such
+    /// circle in rule order.
+    Cycle { pages: Vec<u32> },
+}
 
-    fn get_value_list(&self) -> Vec<String> {
-        match &self.value {
-            Some(v) => vec![v.to_string()],
-            None => self
-                .nodes
-                .iter()
-                .map(|(key, node)| node.get_value_list())
-                .flatten()
-                .collect::<Vec<_>>(),
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixError::Cycle { pages } => {
+                let circle = pages
+                    .iter()
+                    .chain(pages.first())
+                    .map(|page| page.to_string())
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "no order follows every rule, they go round in a circle: {}",
+                    circle.join(" -> ")
+                )
+            }
         }
     }
 }
 
-fn cyclical_tree_build(original_rule: &str, remaining_words: Vec<&str>) -> TrieNode {
-    let mut nodes: HashMap<String, TrieNode> = HashMap::new();
-    let first_letter = remaining_words.first().cloned();
-    let second_letter = remaining_words.first().cloned();
-    let a = if remaining_words.len() > 0 {
-        remaining_words[1..].to_owned()
-    } else {
-        vec![]
-    };
-    let remaining_letters = a;
-
-    let mut curr_trie = match first_letter {
-        Some(first_letter) => {
-            let node = nodes.get(first_letter).cloned();
-            match node {
-                Some(n) => n,
-                None => {
-                    nodes.insert(
-                        first_letter.to_string(),
-                        cyclical_tree_build(original_rule, remaining_letters.clone()),
-                    );
-                    TrieNode { nodes, value: None }
-                }
-            }
-        }
-        None => TrieNode {
-            nodes: HashMap::new(),
-            value: Some(original_rule.to_string()),
-        },
-    };
-    match second_letter {
-        Some(second_letter) => {
-            let node = curr_trie.nodes.get(second_letter).cloned();
-            match node {
-                Some(n) => n,
-                None => {
-                    curr_trie.nodes.insert(
-                        second_letter.to_string(),
-                        cyclical_tree_build(original_rule, remaining_letters),
-                    );
-                    TrieNode {
-                        nodes: curr_trie.nodes.clone(),
-                        value: None,
-                    }
-                }
+impl Error for FixError {}
+
+/// Orders the pages of an update so every rule between them holds, with a topological sort of
+/// the rules restricted to those pages. When several pages could come next the one printed
+/// earliest goes first.
+pub fn fix(graph: &PrecedenceGraph, update: &Update) -> Result<Update, FixError> {
+    let pages = &update.0;
+    let mut positions: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (position, page) in pages.iter().enumerate() {
+        positions.entry(*page).or_default().push(position);
+    }
+    let mut later = vec![vec![]; pages.len()];
+    let mut waiting_on = vec![0; pages.len()];
+    for (position, page) in pages.iter().enumerate() {
+        for after in graph.successors(*page) {
+            for after_position in positions.get(&after).into_iter().flatten() {
+                later[position].push(*after_position);
+                waiting_on[*after_position] += 1;
             }
         }
-        None => TrieNode {
-            nodes: HashMap::new(),
-            value: Some(original_rule.to_string()),
-        },
-    };
-    curr_trie
-}
-
-fn is_rule_valid_on_line(line: &str, rule: &str) -> Option<bool> {
-    println!("checking rule validty {}", rule);
-    let mut split_rules = rule.split("|");
-    let first = split_rules.next().unwrap();
-    let last = split_rules.next().unwrap();
-    let (pos, _) = line
-        .split(",")
-        .enumerate()
-        .find(|(_, letter)| *letter == first)?;
-    let (pos_second, _) = line
-        .split(",")
-        .enumerate()
-        .find(|(_, letter)| *letter == last)?;
-    if pos < pos_second {
-        return Some(true);
     }
-    Some(false)
-}
 
-fn get_valid_rule(node: &TrieNode, word_list: &[String]) -> Option<String> {
-    if word_list.len() == 0 {
-        return node.value.clone();
-    }
-    let current_word = &word_list[0];
-    let new_node = node.nodes.get(current_word);
-    match new_node {
-        Some(new_node) => {
-            let new_list = &word_list[1..];
-            get_valid_rule(new_node, new_list)
+    let mut ready = (0..pages.len())
+        .filter(|position| waiting_on[*position] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut fixed = Vec::with_capacity(pages.len());
+    while let Some(Reverse(position)) = ready.pop() {
+        fixed.push(pages[position]);
+        for after_position in &later[position] {
+            waiting_on[*after_position] -= 1;
+            if waiting_on[*after_position] == 0 {
+                ready.push(Reverse(*after_position));
+            }
         }
-        None => node.value.clone(),
     }
+    if fixed.len() < pages.len() {
+        // the pages that never became ready wait on each other, so they hold a cycle
+        let stuck = (0..pages.len())
+            .filter(|position| waiting_on[*position] > 0)
+            .map(|position| pages[position])
+            .collect::<HashSet<_>>();
+        let cycle = analysis::cycles(&graph.restricted(&stuck))
+            .into_iter()
+            .next()
+            .expect("pages left out of a topological sort are on a cycle");
+        return Err(FixError::Cycle { pages: cycle });
+    }
+    Ok(Update(fixed))
 }
 
-fn get_midpoints_of_lines(lines: &Vec<String>) -> Vec<i32> {
-    lines
+fn fix_lines(graph: &PrecedenceGraph, page: &Page) -> Result<Vec<Update>, FixError> {
+    get_lines_that_dont_comply_rules(graph, page)
         .into_iter()
-        .map(|curr: &String| {
-            let split_lines = curr.split(",").collect::<Vec<_>>();
-            split_lines[split_lines.len() / 2].parse::<i32>().unwrap()
-        })
-        .collect::<Vec<_>>()
+        .map(|line| fix(graph, line))
+        .collect()
 }
 
-fn fix_line(line: &Vec<String>, rules: &Vec<String>) -> String {
-    println!("line to fix: {}", line.join(","));
-    let invalid_rules = rules
-        .iter()
-        .filter(|rule| {
-            let split_line = &line.join(",");
-            !is_rule_valid_on_line(split_line, rule.as_str()).unwrap()
-        })
-        .collect::<Vec<_>>();
-    if invalid_rules.len() == 0 {
-        println!("no invalid rules");
-        return line.join(",");
-    }
-    println!("invalid rules {:?}", invalid_rules);
-    let rule_to_fix = invalid_rules[0].clone();
-    println!("rule to fix: {}", rule_to_fix);
-    let fixed_line_after_rule = fix_rule(&line, &rule_to_fix)
-        .split(",")
-        .map(|l| l.to_string())
-        .collect::<Vec<_>>();
-    println!("old line: {}", line.join(","));
-    println!("fixed line: {}", fixed_line_after_rule.join(","));
-    fix_line(&fixed_line_after_rule, rules)
-}
+pub struct Day5;
 
-fn fix_rule(line: &Vec<String>, rule: &String) -> String {
-    let split_rules = rule.split("|").collect::<Vec<_>>();
-    let first_number_of_rule = split_rules[0];
-    let second_number_of_rule = split_rules[1];
-    let first_number_position_in_vec = line.iter().position(|n| n == first_number_of_rule).unwrap();
-    let second_number_position_in_vec = line
-        .iter()
-        .position(|n| n == second_number_of_rule)
-        .unwrap();
-    let new_string = move_item_in_vec(
-        line,
-        second_number_position_in_vec,
-        first_number_position_in_vec,
-    );
-    let rule_as_string = new_string.join(",");
-    println!("fixed rule: {:#}", rule_as_string);
-    rule_as_string
-}
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = Page;
 
-fn move_item_in_vec<T: Clone>(vec: &Vec<T>, origin: usize, destination: usize) -> Vec<T> {
-    let item = vec[origin].clone();
-    let slice_without_item = [&vec[..origin], &vec[origin + 1..]].concat();
-    [
-        &slice_without_item[..destination],
-        &[item],
-        &slice_without_item[destination..],
-    ]
-    .concat()
-}
+    fn parse(input: &str) -> Result<Page, SolutionError> {
+        Ok(parse_page(input)?)
+    }
+
+    fn part1(page: &Page) -> Result<Answer, SolutionError> {
+        let graph = PrecedenceGraph::new(&page.rules);
+        let lines = get_lines_that_comply_rules(&graph, page)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<Update>>();
+        Ok(get_midpoints_of_lines(&lines)
+            .into_iter()
+            .sum::<u32>()
+            .into())
+    }
 
-pub fn main(input: String) {
-  
+    fn part2(page: &Page) -> Result<Answer, SolutionError> {
+        let graph = PrecedenceGraph::new(&page.rules);
+        let fixed_lines = fix_lines(&graph, page)?;
+        Ok(get_midpoints_of_lines(&fixed_lines)
+            .into_iter()
+            .sum::<u32>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use core::fmt;
-    use std::string;
-
     use super::*;
+    use crate::util::util;
+
+    fn read_page(file: &str) -> Page {
+        parse_page(&util::read_input("day5", file).unwrap()).unwrap()
+    }
+
     #[test]
     fn test_read_input() {
-        let page = extract_rules_and_rows_from_input("test.txt");
-        assert_eq!(page.raw_rules.len(), 21);
-        assert_eq!(page.raw_lines.len(), 6);
+        let page = read_page("test.txt");
+        assert_eq!(page.rules.len(), 21);
+        assert_eq!(page.updates.len(), 6);
+        assert_eq!(
+            page.rules[0],
+            Rule {
+                before: 47,
+                after: 53
+            }
+        );
+        assert_eq!(page.updates[2], Update(vec![75, 29, 13]));
+    }
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_page("1|2\n3|4|5\n"),
+            Err(ParseError::MalformedRule {
+                line: 2,
+                text: "3|4|5".to_string()
+            })
+        );
+        assert_eq!(
+            parse_page("1|2\n\n1,x,3\n"),
+            Err(ParseError::NotANumber {
+                line: 3,
+                text: "x".to_string()
+            })
+        );
+        let error = parse_page("1|2\n\n1,2\n2|3\n").unwrap_err();
+        assert_eq!(error, ParseError::RuleAfterUpdates { line: 4 });
+        assert_eq!(
+            error.to_string(),
+            "line 4: rules have to come before the updates"
+        );
     }
     #[test]
     fn test_build_rules() {
-        let page = extract_rules_and_rows_from_input("test.txt");
-        let rule_trie = RuleTrie::new(&page);
-        let lines = rule_trie
-            .get_lines_that_comply_rules()
-            .iter()
-            .map(|line| line.0.clone())
-            .collect::<Vec<String>>();
-        let sum: i32 = get_midpoints_of_lines(&lines).into_iter().sum();
+        let page = read_page("test.txt");
+        let graph = PrecedenceGraph::new(&page.rules);
+        let lines = get_lines_that_comply_rules(&graph, &page)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<Update>>();
+        let sum: u32 = get_midpoints_of_lines(&lines).into_iter().sum();
         println!("Sum of midpoints: {}", sum);
         assert_eq!(sum, 143);
     }
     #[test]
     fn test_build_rules_2() {
-        let page = extract_rules_and_rows_from_input("test.txt");
-        let rule_trie = RuleTrie::new(&page);
-        let lines = rule_trie.get_lines_that_dont_comply_rules();
-        println!(
-            "lines to fix: {:?}",
-            lines
-                .iter()
-                .map(|l| {
-                    let aas = l.0.clone();
-                    aas
-                })
-                .collect::<Vec<_>>()
-        );
-        let fixed_lines = lines
-            .iter()
-            .map(|(line, rules)| {
-                let split_line = line.split(",").map(|l| l.to_string()).collect::<Vec<_>>();
-                let rules = rules
-                    .iter()
-                    .map(|rule| rule.unwrap().get_value_list().first().unwrap().to_string())
-                    .collect::<Vec<_>>();
-                println!("line to fix: {}", line);
-                fix_line(&split_line, &rules)
-            })
-            .collect::<Vec<_>>();
-        println!("{:#}", fixed_lines.join("\n"));
-        let sum: i32 = get_midpoints_of_lines(&fixed_lines).into_iter().sum();
+        let page = read_page("test.txt");
+        let graph = PrecedenceGraph::new(&page.rules);
+        let fixed_lines = fix_lines(&graph, &page).unwrap();
+        let sum: u32 = get_midpoints_of_lines(&fixed_lines).into_iter().sum();
         println!("Sum of midpoints: {}", sum);
         assert_eq!(sum, 123);
     }
     #[test]
-    fn test_answer() {
-        let page = extract_rules_and_rows_from_input("input.txt");
-        let rule_trie = RuleTrie::new(&page);
-        let lines = rule_trie
-            .get_lines_that_comply_rules()
-            .iter()
-            .map(|line| line.0.clone())
-            .collect::<Vec<String>>();
-        let sum: i32 = get_midpoints_of_lines(&lines).into_iter().sum();
-        println!("Sum of midpoints: {}", sum);
-        assert_eq!(sum, 4609);
-    }
-    #[test]
-    fn test_answer_2() {
-        let page = extract_rules_and_rows_from_input("input.txt");
-        let rule_trie = RuleTrie::new(&page);
-        let lines = rule_trie.get_lines_that_dont_comply_rules();
-        println!(
-            "lines to fix: {:?}",
-            lines
-                .iter()
-                .map(|l| {
-                    let aas = l.0.clone();
-                    aas
-                })
-                .collect::<Vec<_>>()
+    fn test_validate() {
+        let page = read_page("test.txt");
+        let graph = PrecedenceGraph::new(&page.rules);
+        assert_eq!(validate(&graph, &page.updates[0]), Ok(()));
+        let violation = validate(&graph, &page.updates[3]).unwrap_err();
+        assert_eq!(
+            violation,
+            Violation {
+                rule: Rule {
+                    before: 97,
+                    after: 75
+                },
+                before_position: 1,
+                after_position: 0
+            }
         );
-        let fixed_lines = lines
-            .iter()
-            .map(|(line, rules)| {
-                let split_line = line.split(",").map(|l| l.to_string()).collect::<Vec<_>>();
-                let rules = rules
-                    .iter()
-                    .map(|rule| rule.unwrap().get_value_list().first().unwrap().to_string())
-                    .collect::<Vec<_>>();
-                println!("line to fix: {}", line);
-                fix_line(&split_line, &rules)
+        assert_eq!(
+            violation.to_string(),
+            "97|75 is broken, page 75 is at position 0 and page 97 at position 1"
+        );
+
+        // a chain of rules over thousands of pages
+        let rules = (0..5000)
+            .map(|page| Rule {
+                before: page,
+                after: page + 1,
             })
             .collect::<Vec<_>>();
-        println!("{:#}", fixed_lines.join("\n"));
-        let sum: i32 = get_midpoints_of_lines(&fixed_lines).into_iter().sum();
-        println!("Sum of midpoints: {}", sum);
-        assert_eq!(sum, 5723);
+        let graph = PrecedenceGraph::new(&rules);
+        assert_eq!(validate(&graph, &Update((0..=5000).collect())), Ok(()));
+        let reversed = validate(&graph, &Update((0..=5000).rev().collect())).unwrap_err();
+        assert_eq!((reversed.before_position, reversed.after_position), (1, 0));
+    }
+    #[test]
+    fn test_fix() {
+        let page = read_page("test.txt");
+        let graph = PrecedenceGraph::new(&page.rules);
+        assert_eq!(fix(&graph, &page.updates[0]), Ok(page.updates[0].clone()));
+        assert_eq!(
+            fix(&graph, &page.updates[3]),
+            Ok(Update(vec![97, 75, 47, 61, 53]))
+        );
+        assert_eq!(fix(&graph, &page.updates[4]), Ok(Update(vec![61, 29, 13])));
+        assert_eq!(
+            fix(&graph, &page.updates[5]),
+            Ok(Update(vec![97, 75, 47, 29, 13]))
+        );
+        // 5 and 7 are not ordered by any rule
+        assert_eq!(
+            fix(&graph, &Update(vec![7, 13, 5, 97])),
+            Ok(Update(vec![7, 5, 97, 13]))
+        );
+
+        let mut graph = graph;
+        graph.add(Rule {
+            before: 13,
+            after: 97,
+        });
+        let error = fix(&graph, &Update(vec![61, 13, 97])).unwrap_err();
+        assert_eq!(
+            error,
+            FixError::Cycle {
+                pages: vec![13, 97]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "no order follows every rule, they go round in a circle: 13 -> 97 -> 13"
+        );
     }
 }
@@ -0,0 +1,91 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::day5::day5::Rule;
+
+/// The ordering rules as a directed graph, with an edge from every page to each page that has to
+/// come after it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrecedenceGraph {
+    successors: HashMap<u32, HashSet<u32>>,
+}
+
+impl PrecedenceGraph {
+    pub fn new(rules: &[Rule]) -> PrecedenceGraph {
+        let mut graph = PrecedenceGraph::default();
+        for rule in rules {
+            graph.add(*rule);
+        }
+        graph
+    }
+
+    pub fn add(&mut self, rule: Rule) {
+        self.successors
+            .entry(rule.before)
+            .or_default()
+            .insert(rule.after);
+    }
+
+    /// Whether a rule says `before` has to come first, only direct rules count.
+    pub fn must_precede(&self, before: u32, after: u32) -> bool {
+        self.successors
+            .get(&before)
+            .is_some_and(|successors| successors.contains(&after))
+    }
+
+    /// The pages that have to come after `page`.
+    pub fn successors(&self, page: u32) -> impl Iterator<Item = u32> + '_ {
+        self.successors.get(&page).into_iter().flatten().copied()
+    }
+
+    /// Every page that appears in a rule, on either side.
+    pub fn pages(&self) -> HashSet<u32> {
+        self.successors
+            .iter()
+            .flat_map(|(before, after)| std::iter::once(*before).chain(after.iter().copied()))
+            .collect()
+    }
+
+    /// The rules between `pages` only.
+    pub fn restricted(&self, pages: &HashSet<u32>) -> PrecedenceGraph {
+        PrecedenceGraph::new(
+            &self
+                .rules()
+                .filter(|rule| pages.contains(&rule.before) && pages.contains(&rule.after))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = Rule> + '_ {
+        self.successors.iter().flat_map(|(before, after)| {
+            after.iter().map(|after| Rule {
+                before: *before,
+                after: *after,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precedence_queries() {
+        let rule = |before, after| Rule { before, after };
+        let graph = PrecedenceGraph::new(&[rule(47, 53), rule(97, 13), rule(97, 47), rule(47, 53)]);
+        assert!(graph.must_precede(47, 53));
+        assert!(!graph.must_precede(53, 47));
+        // rules are not chained, 97|47 and 47|53 do not make 97|53
+        assert!(!graph.must_precede(97, 53));
+
+        let mut successors = graph.successors(97).collect::<Vec<_>>();
+        successors.sort();
+        assert_eq!(successors, vec![13, 47]);
+        assert_eq!(graph.successors(13).count(), 0);
+        assert_eq!(graph.pages(), HashSet::from([13, 47, 53, 97]));
+        assert_eq!(graph.rules().count(), 3);
+        let restricted = graph.restricted(&HashSet::from([13, 47, 97]));
+        assert_eq!(restricted.pages(), HashSet::from([13, 47, 97]));
+        assert!(!restricted.must_precede(47, 53));
+    }
+}
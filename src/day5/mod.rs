@@ -1 +1,3 @@
+pub mod analysis;
 pub mod day5;
+pub mod graph;
@@ -1,87 +1,88 @@
-use std::{
-    cell::RefCell,
-    collections::HashMap,
-    fmt,
-    ptr::eq,
-    rc::Rc,
-    sync::{Arc, Mutex},
+use std::{collections::HashSet, error::Error, fmt};
+
+use crate::{
+    solution::solution::{Answer, Solution, SolutionError},
+    util::{
+        geometry::{Direction4, Point},
+        grid::{Grid, GridError},
+    },
 };
-
-use crate::util::util;
 use rayon::prelude::*;
 
-fn extract_matrices_from_input(input: &str) -> Matrices {
-    let lines = util::read_input("day6", input);
-    println!(
-        "lines: {}",
-        lines.lines().enumerate().collect::<Vec<_>>().len()
-    );
-    lines.lines().enumerate().fold(
-        Matrices {
-            visit_matrix: Vec::new(),
-            guard_path: Vec::new(),
-            node_matrix: Vec::new(),
-        },
-        |mut acc, (x, curr)| {
-            // println!("matrix len: {}", acc.visit_matrix.len());
-            // println!("matrix len: {}", acc.node_matrix.len());
-            acc.visit_matrix
-                .push(vec![VisitDirections(Vec::new()); curr.len()]);
-            // println!(
-            //     "visit matrix {}: {:?}, len: {}",
-            //     x,
-            //     acc.visit_matrix[x],
-            //     acc.visit_matrix[x].len()
-            // );
-            acc.node_matrix.push(vec![
-                Node {
-                    node_type: NodeType::EMPTY,
-                    position: Position { x: 0, y: 0 }
-                };
-                curr.len()
-            ]);
-            curr.char_indices().fold(acc, |mut acc, (y, c)| {
-                let position = Position {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                let node = Node::from(c, position);
-                acc.node_matrix[x][y] = node;
-                Matrices {
-                    visit_matrix: acc.visit_matrix,
-                    guard_path: acc.guard_path,
-                    node_matrix: acc.node_matrix,
-                }
-            })
-        },
-    )
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Grid(GridError),
+    NoGuard,
 }
 
-type Matrix<T> = Vec<Row<T>>;
-type Row<T> = Vec<T>;
-
-struct Matrices {
-    visit_matrix: Vec<Vec<VisitDirections>>,
-    guard_path: Vec<Path>,
-    node_matrix: Vec<Vec<Node>>,
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Grid(error) => write!(f, "{}", error),
+            ParseError::NoGuard => write!(f, "the map has no guard"),
+        }
+    }
 }
 
-impl Clone for Matrices {
-    fn clone(&self) -> Self {
-        Self {
-            visit_matrix: deep_copy_matrix(&self.visit_matrix),
-            guard_path: self.guard_path.clone(),
-            node_matrix: deep_copy_matrix(&self.node_matrix),
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Grid(error) => Some(error),
+            ParseError::NoGuard => None,
         }
     }
 }
 
+impl From<GridError> for ParseError {
+    fn from(error: GridError) -> Self {
+        ParseError::Grid(error)
+    }
+}
+
+/// The way a guard symbol faces, `None` for anything that is not a guard.
+fn facing(symbol: char) -> Option<Direction4> {
+    match symbol {
+        '^' => Some(Direction4::Up),
+        '>' => Some(Direction4::Right),
+        'v' => Some(Direction4::Down),
+        '<' => Some(Direction4::Left),
+        _ => None,
+    }
+}
+
+fn extract_matrices_from_input(lines: &str) -> Result<Matrices, ParseError> {
+    let symbols = Grid::parse(lines, |c| c)?;
+    let guard = symbols
+        .find(|symbol| facing(*symbol).is_some())
+        .ok_or(ParseError::NoGuard)?;
+    let node_matrix = symbols.map(|_, symbol| Node::from(*symbol));
+    Ok(Matrices {
+        visit_matrix: Grid::new(
+            node_matrix.width(),
+            node_matrix.height(),
+            VisitDirections(Vec::new()),
+        ),
+        guard_path: Vec::new(),
+        guard_direction: facing(symbols[guard]).unwrap(),
+        node_matrix,
+    })
+}
+
+#[derive(Clone)]
+pub struct Matrices {
+    visit_matrix: Grid<VisitDirections>,
+    guard_path: Vec<Path>,
+    /// Where the guard faces before the first step.
+    guard_direction: Direction4,
+    node_matrix: Grid<Node>,
+}
+
 #[derive(Clone)]
-struct VisitDirections(Vec<Direction>);
+struct VisitDirections(Vec<Direction4>);
 
-impl ToString for VisitDirections {
-    fn to_string(&self) -> String {
-        self.0.len().to_string()
+impl fmt::Display for VisitDirections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.len())
     }
 }
 
@@ -92,93 +93,29 @@ enum NodeType {
     EMPTY,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-}
-
-impl Direction {
-    pub fn arrow(&self) -> String {
-        match self {
-            Direction::LEFT => "L",
-            Direction::RIGHT => "R",
-            Direction::UP => "U",
-            Direction::DOWN => "D",
-        }
-        .to_owned()
-    }
-}
-
 impl Node {
-    pub fn from(c: char, position: Position<i32>) -> Node {
+    pub fn from(c: char) -> Node {
         match c {
             '#' => Node {
                 node_type: NodeType::OBSTACLE,
-                position,
             },
             '.' => Node {
                 node_type: NodeType::EMPTY,
-                position,
             },
             '^' => Node {
                 node_type: NodeType::GUARD,
-                position,
             },
             '>' => Node {
                 node_type: NodeType::GUARD,
-                position,
             },
             '<' => Node {
                 node_type: NodeType::GUARD,
-                position,
             },
             'v' => Node {
                 node_type: NodeType::GUARD,
-                position,
             },
             _ => Node {
                 node_type: NodeType::EMPTY,
-                position,
-            },
-        }
-    }
-    fn calculate_new_direction(&self, current_direction: &Direction) -> Direction {
-        match current_direction {
-            Direction::UP => Direction::RIGHT,
-            Direction::DOWN => Direction::LEFT,
-            Direction::LEFT => Direction::UP,
-            Direction::RIGHT => Direction::DOWN,
-        }
-    }
-}
-
-#[derive(Clone, PartialEq, Debug, Eq, Hash)]
-struct Position<T> {
-    x: T,
-    y: T,
-}
-
-impl Position<i32> {
-    pub fn move_to_direction(&self, direction: &Direction) -> Position<i32> {
-        match direction {
-            Direction::LEFT => Position {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::RIGHT => Position {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::UP => Position {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Direction::DOWN => Position {
-                x: self.x + 1,
-                y: self.y,
             },
         }
     }
@@ -187,198 +124,118 @@ impl Position<i32> {
 #[derive(Clone)]
 struct Node {
     node_type: NodeType,
-    position: Position<i32>,
 }
 
-impl ToString for Node {
-    fn to_string(&self) -> String {
-        match self.node_type {
-            NodeType::EMPTY => ".".to_owned(),
-            NodeType::GUARD => "G".to_owned(),
-            NodeType::OBSTACLE => "X".to_owned(),
-        }
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self.node_type {
+            NodeType::EMPTY => ".",
+            NodeType::GUARD => "G",
+            NodeType::OBSTACLE => "X",
+        };
+        write!(f, "{}", symbol)
     }
 }
 
 #[derive(Clone)]
 struct Path {
-    nodes: Vec<(Node, Direction)>,
-    start: (Position<i32>, Direction),
-    end: Option<(Position<i32>, Direction)>,
-    visit_matrix: Vec<Vec<VisitDirections>>,
+    nodes: Vec<(Node, Direction4)>,
+    start: (Point, Direction4),
+    end: Option<(Point, Direction4)>,
 }
 
 impl Path {
     fn new() -> Path {
         Path {
             nodes: Vec::new(),
-            start: (Position { x: -1, y: -1 }, Direction::UP),
+            start: (Point::new(-1, -1), Direction4::Up),
             end: None,
-            visit_matrix: Vec::new(),
         }
     }
 }
 
 impl Matrices {
-    fn find_guard(&self) -> Option<Position<i32>> {
-        for x in 0..self.node_matrix.len() {
-            for y in 0..self.node_matrix.len() {
-                if self.node_matrix[x][y].node_type == NodeType::GUARD {
-                    return Some(Position {
-                        x: x as i32,
-                        y: y as i32,
-                    });
-                }
-            }
-        }
-        None
+    fn is_free(&self, position: &Point) -> bool {
+        self.node_matrix
+            .get(*position)
+            .is_some_and(|node| node.node_type != NodeType::OBSTACLE)
     }
 
-    fn extract_intersection(
-        &self,
-        guard_position: &Position<i32>,
-        direction: &Direction,
-    ) -> (Row<Node>, Row<Node>) {
-        let column = get_column_of_matrix(&self.node_matrix, guard_position.y as usize);
-        let row = self.node_matrix[guard_position.x as usize].to_vec();
-        match direction {
-            Direction::UP | Direction::DOWN => (column, row),
-            Direction::LEFT | Direction::RIGHT => (row, column),
-        }
-    }
-
-    fn has_candidate_for_obstruction(
-        &self,
-        guard_position: &Position<i32>,
-        direction: &Direction,
-    ) -> Option<Position<i32>> {
-        let (mut current_row, perpendicular_row) =
-            self.extract_intersection(guard_position, direction);
-
-        let current_guard_position = current_row
-            .iter()
-            .position(|node| node.node_type == NodeType::GUARD)
-            .unwrap();
-        match direction {
-            Direction::UP | Direction::LEFT => {
-                current_row.reverse();
-            }
-            Direction::DOWN | Direction::RIGHT => {}
-        };
-        let has_obstacle_before = current_row[current_guard_position..]
-            .iter()
-            .any(|node| node.node_type == NodeType::OBSTACLE);
-        let has_2_obstacles_perpendicular = perpendicular_row
-            .iter()
-            .filter(|node| node.node_type == NodeType::OBSTACLE)
-            .count()
-            >= 2;
-        if has_2_obstacles_perpendicular && has_obstacle_before {
-            let current_guard_node = &current_row[current_guard_position];
-            let obstacle_node_position = current_guard_node.position.move_to_direction(direction);
-            return Some(obstacle_node_position);
-        }
-        None
+    fn find_guard(&self) -> Option<Point> {
+        self.node_matrix
+            .find(|node| node.node_type == NodeType::GUARD)
     }
 
     fn navigate_and_get_direction(
         &mut self,
-        direction: &Direction,
-        guard_position: &Position<i32>,
-    ) -> (Option<Direction>, Option<Position<i32>>) {
-        let new_position = guard_position.move_to_direction(direction);
-        if new_position.x < 0
-            || new_position.y < 0
-            || new_position.x >= self.node_matrix.len() as i32
-            || new_position.y >= self.node_matrix.len() as i32
-        {
+        direction: &Direction4,
+        guard_position: &Point,
+    ) -> (Option<Direction4>, Option<Point>) {
+        let new_position = *guard_position + direction.delta();
+        if !self.node_matrix.contains(new_position) {
             // Exit map
-            let current_node =
-                &mut self.node_matrix[guard_position.x as usize][guard_position.y as usize];
+            let current_node = &mut self.node_matrix[*guard_position];
             current_node.node_type = NodeType::EMPTY;
-            self.visit_matrix[guard_position.x as usize][guard_position.y as usize]
-                .0
-                .push(direction.clone());
-            let last_pos = self.guard_path.len() - 1;
-            let last_path_nodes = &mut self.guard_path[last_pos].nodes;
-            last_path_nodes.push((current_node.clone(), direction.clone()));
-            let last_path = self.guard_path.last_mut();
-            match last_path {
-                Some(last_path) => {
-                    last_path.visit_matrix = deep_copy_matrix(&self.visit_matrix);
-                    last_path.end = Some((guard_position.clone(), direction.clone()))
-                }
-                None => {}
+            self.visit_matrix[*guard_position].0.push(*direction);
+            // a guard that leaves on its first step has no path yet
+            if self.guard_path.is_empty() {
+                let mut new_path = Path::new();
+                new_path.start = (*guard_position, *direction);
+                self.guard_path.push(new_path);
+            }
+            if let Some(last_path) = self.guard_path.last_mut() {
+                last_path.nodes.push((current_node.clone(), *direction));
+                last_path.end = Some((*guard_position, *direction))
             };
-            return (None, None);
-        } else if self.node_matrix[new_position.x as usize][new_position.y as usize].node_type
-            == NodeType::OBSTACLE
-        {
+            (None, None)
+        } else if self.node_matrix[new_position].node_type == NodeType::OBSTACLE {
             // Turn around
-            let current_node =
-                &self.node_matrix[guard_position.x as usize][guard_position.y as usize];
-            self.visit_matrix[guard_position.x as usize][guard_position.y as usize]
-                .0
-                .push(direction.clone());
+            let current_node = &self.node_matrix[*guard_position];
+            self.visit_matrix[*guard_position].0.push(*direction);
 
             let last_path = self.guard_path.last_mut();
-            match last_path {
-                Some(last_path) => {
-                    last_path.end = Some((guard_position.clone(), direction.clone()))
-                }
-                None => {}
+            if let Some(last_path) = last_path {
+                last_path.end = Some((*guard_position, *direction))
             };
 
             let mut new_path = Path::new();
             let last_path_nodes = &mut new_path.nodes;
-            new_path.start = (guard_position.clone(), direction.clone());
-            new_path.visit_matrix = deep_copy_matrix(&self.visit_matrix);
-            last_path_nodes.push((current_node.clone(), direction.clone()));
+            new_path.start = (*guard_position, *direction);
+            last_path_nodes.push((current_node.clone(), *direction));
             self.guard_path.push(new_path);
-            // self.navigate_guard();
-            let direction = (&current_node).calculate_new_direction(direction);
-            return (Some(direction), Some(guard_position.clone()));
+            let direction = direction.turn_right();
+            (Some(direction), Some(*guard_position))
         } else {
             // Move forward
-            let current_node =
-                &mut self.node_matrix[guard_position.x as usize][guard_position.y as usize];
+            let current_node = &mut self.node_matrix[*guard_position];
             current_node.node_type = NodeType::EMPTY;
-            let new_node = &mut self.node_matrix[new_position.x as usize][new_position.y as usize];
+            let new_node = &mut self.node_matrix[new_position];
             new_node.node_type = NodeType::GUARD;
-            let current_node =
-                &self.node_matrix[guard_position.x as usize][guard_position.y as usize];
-            self.visit_matrix[guard_position.x as usize][guard_position.y as usize]
-                .0
-                .push(direction.clone());
+            let current_node = &self.node_matrix[*guard_position];
+            self.visit_matrix[*guard_position].0.push(*direction);
             let guard_path = &mut self.guard_path;
             let last_path = guard_path.last_mut();
             match last_path {
                 Some(last_path) => {
-                    last_path
-                        .nodes
-                        .push((current_node.clone(), direction.clone()));
+                    last_path.nodes.push((current_node.clone(), *direction));
                 }
                 None => {
                     let mut new_path: Path = Path::new();
-                    new_path
-                        .nodes
-                        .push((current_node.clone(), direction.clone()));
-                    new_path.start = (guard_position.clone(), direction.clone());
-                    new_path.visit_matrix = deep_copy_matrix(&self.visit_matrix);
+                    new_path.nodes.push((current_node.clone(), *direction));
+                    new_path.start = (*guard_position, *direction);
                     guard_path.push(new_path.clone());
                 }
             };
-            // self.navigate_guard(direction);
-            return (Some(direction.clone()), Some(new_position.clone()));
+            (Some(*direction), Some(new_position))
         }
     }
 }
 
 fn navigate(
     mut matrices: Matrices,
-    direction: Option<&Direction>,
-    current_position: Option<&Position<i32>>,
-    can_continue: impl Fn(&Matrices, Option<&Position<i32>>, Option<&Direction>) -> bool,
+    direction: Option<&Direction4>,
+    current_position: Option<&Point>,
+    mut can_continue: impl FnMut(&Matrices, Option<&Point>, Option<&Direction4>) -> bool,
 ) -> Matrices {
     let mut curr_direction = direction.cloned();
     let mut current_position = current_position.cloned();
@@ -397,295 +254,152 @@ fn navigate(
     matrices
 }
 
-fn get_column_of_matrix<T: Clone>(matrix: &Vec<Vec<T>>, column: usize) -> Vec<T> {
-    let mut vec: Vec<T> = Vec::with_capacity(matrix.len());
-    for x in 0..matrix.len() {
-        for y in 0..matrix[x].len() {
-            if y == column {
-                vec[x] = matrix[x][y].clone();
-            }
-        }
-    }
-    vec
-}
-
-fn print_matrix<T: ToString>(matrix: &Vec<Vec<T>>) -> String {
-    matrix
-        .iter()
-        .map(|row| {
-            format!(
-                "{}\n",
-                row.iter()
-                    .fold("".to_string(), |acc, curr| acc + &curr.to_string())
-            )
-        })
-        .fold("".to_string(), |a, curr| a + curr.as_str())
-}
-
-fn deep_copy_matrix<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    matrix
-        .iter()
-        .map(|row| row.iter().map(|cell| cell.clone()).collect())
-        .collect()
-}
-
-fn print_path(
-    matrices: &Matrices,
-    obstruction_position: &Position<i32>,
-    guard_position: &Position<i32>,
-) -> String {
-    let node_matrix = &matrices.node_matrix;
-    let paths = &matrices.guard_path;
-    let mut matrix = vec![vec![".".to_owned(); node_matrix.len()]; node_matrix.len()];
-    paths.into_iter().for_each(|path| {
-        path.nodes.iter().for_each(|(node, direction)| {
-            let pos = &node.position;
-            matrix[pos.x as usize][pos.y as usize] = direction.arrow();
-        });
-    });
-    for x in 0..matrix.len() {
-        for y in 0..matrix.len() {
-            if node_matrix[x][y].node_type == NodeType::OBSTACLE {
-                matrix[x][y] = "O".to_string();
-            }
-        }
-    }
-    matrix[obstruction_position.x as usize][obstruction_position.y as usize] = "X".to_string();
-    matrix[guard_position.x as usize][guard_position.y as usize] = "G".to_string();
-    let loop_last_position = &matrices
-        .guard_path
-        .last()
-        .unwrap()
-        .nodes
-        .last()
-        .unwrap()
-        .0
-        .position;
-    matrix[loop_last_position.x as usize][loop_last_position.y as usize] = "W".to_string();
-    print_matrix(&matrix)
-}
-
 fn add_obstruction_in_front(
     matrix: &mut Matrices,
-    guard_position: &Position<i32>,
-    direction: &Direction,
-    already_visited: &Matrix<VisitDirections>,
-) -> Option<Position<i32>> {
-    let obstruction_position = guard_position.move_to_direction(direction);
-    if obstruction_position.x as usize >= matrix.node_matrix.len()
-        || obstruction_position.y as usize >= matrix.node_matrix.len()
-    {
-        return None;
-    }
-    if already_visited[obstruction_position.x as usize][obstruction_position.y as usize]
-        .0
-        .len()
-        > 0
-    {
+    guard_position: &Point,
+    direction: &Direction4,
+) -> Option<Point> {
+    let obstruction_position = *guard_position + direction.delta();
+    if !matrix.is_free(&obstruction_position) {
         return None;
     }
-    matrix.node_matrix[obstruction_position.x as usize][obstruction_position.y as usize]
-        .node_type = NodeType::OBSTACLE;
+    matrix.node_matrix[obstruction_position].node_type = NodeType::OBSTACLE;
     Some(obstruction_position)
 }
 
-fn remove_obstruction_from_position(matrix: &mut Matrices, position: &Position<i32>) {
-    matrix.node_matrix[position.x as usize][position.y as usize].node_type = NodeType::EMPTY;
-}
-
-pub fn part_2() {
-    let mut matrices = extract_matrices_from_input("test.txt");
+fn part_1(matrices: &Matrices) -> usize {
+    let mut matrices = matrices.clone();
     let guard_position = matrices.find_guard().unwrap();
+    let guard_direction = matrices.guard_direction;
     let (direction, guard_position) =
-        matrices.navigate_and_get_direction(&Direction::UP, &guard_position);
+        matrices.navigate_and_get_direction(&guard_direction, &guard_position);
     let matrices = navigate(
         matrices,
         direction.as_ref(),
         guard_position.as_ref(),
         |_, _, direction| direction.is_some(),
     );
-    let matrix_string = print_matrix(&matrices.visit_matrix);
-    print!("{}", matrix_string);
-    let uniques: Vec<(Position<i32>, VisitDirections)> = matrices
+    matrices
         .visit_matrix
-        .iter()
-        .enumerate()
-        .map(|(x, row)| {
-            row.iter()
-                .enumerate()
-                .filter_map(move |(y, col)| match col.0.len() > 0 {
-                    true => Some((
-                        Position {
-                            x: x.clone() as i32,
-                            y: y.clone() as i32,
-                        },
-                        col.clone(),
-                    )),
-                    false => None,
-                })
+        .values()
+        .filter(|number| !number.0.is_empty())
+        .count()
+}
+
+fn part_2(matrices: &Matrices) -> usize {
+    let mut matrices = matrices.clone();
+    let guard_start = matrices.find_guard().unwrap();
+    let guard_direction = matrices.guard_direction;
+    let mut guard_states = vec![(guard_start, guard_direction)];
+    let (direction, guard_position) =
+        matrices.navigate_and_get_direction(&guard_direction, &guard_start);
+    let matrices = navigate(
+        matrices,
+        direction.as_ref(),
+        guard_position.as_ref(),
+        |_, guard_position, direction| match (guard_position, direction) {
+            (Some(guard_position), Some(direction)) => {
+                guard_states.push((*guard_position, *direction));
+                true
+            }
+            _ => false,
+        },
+    );
+    // an obstruction only changes the route from the first time the guard walks into it,
+    // so every cell is tried once, from the step right before the guard first reached it
+    let mut obstructed = HashSet::from([guard_start]);
+    let candidates: Vec<(Point, Direction4)> = guard_states
+        .into_iter()
+        .filter(|(guard_position, direction)| {
+            let obstruction_position = *guard_position + direction.delta();
+            matrices.is_free(&obstruction_position) && obstructed.insert(obstruction_position)
         })
-        .flatten()
         .collect();
 
-    let total_obstructions: usize = uniques
+    candidates
         .par_iter()
-        .map(|(start_position, directions)| {
-            let mut unique_directions = directions.0.clone();
-            unique_directions.dedup_by(|a, b| a == b);
-            // println!("Directions to parallelize: {:#}", unique_directions.len());
-            unique_directions
-                .par_iter()
-                .filter(|direction| {
-                    // creating a new matrix each time so I can use my navigate function without any concerns
-                    // side effects, what?! who?
-                    let mut matrix = matrices.clone();
-                    let already_visited =
-                        deep_copy_matrix(&matrix.guard_path.last().unwrap().visit_matrix);
-                    matrix.guard_path = vec![];
-                    // also add the position of the guard in this instant
-                    matrix.node_matrix[start_position.x as usize][start_position.y as usize]
-                        .node_type = NodeType::GUARD;
-
-                    let obstruction_position = add_obstruction_in_front(
-                        &mut matrix,
-                        start_position,
-                        direction,
-                        &already_visited,
-                    );
-                    if obstruction_position.is_none() {
-                        return false;
-                    }
-                    let obstruction_position = obstruction_position.unwrap();
-
-                    let did_loop = Arc::new(Mutex::new(false));
-                    let (direction, guard_position) =
-                        matrix.navigate_and_get_direction(direction, &start_position);
-
-                    if direction.is_some() {
-                        let new_guard_position = start_position;
-                        if new_guard_position.x >= 0
-                            || new_guard_position.y >= 0
-                            || new_guard_position.x < matrix.node_matrix.len() as i32
-                            || new_guard_position.y < matrix.node_matrix.len() as i32
-                        {
-                            if matrix.node_matrix[new_guard_position.x as usize]
-                                [new_guard_position.y as usize]
-                                .node_type
-                                != NodeType::OBSTACLE
-                            {
-                                let visit_map: Arc<
-                                    Mutex<
-                                        HashMap<
-                                            (
-                                                Direction,
-                                                Position<i32>,
-                                                Position<i32>,
-                                                (Position<i32>, Direction),
-                                            ),
-                                            bool,
-                                        >,
-                                    >,
-                                > = Arc::new(Mutex::new(HashMap::new()));
-                                matrix = navigate(
-                                    matrix.clone(),
-                                    direction.as_ref(),
-                                    guard_position.as_ref(),
-                                    |matrix, guard_position, direction| match direction {
-                                        Some(direction) => {
-                                            // if *guard_position.unwrap() == start_position.clone()
-                                            //     && matrix.guard_path.last().unwrap().nodes.len() > 1
-                                            // {
-                                            // }
-                                            let last_path = matrix.guard_path.last().unwrap();
-                                            if visit_map
-                                                .lock()
-                                                .unwrap()
-                                                .get(&(
-                                                    direction.clone(),
-                                                    guard_position.unwrap().clone(),
-                                                    obstruction_position.clone(),
-                                                    last_path.start.clone(),
-                                                ))
-                                                .is_some()
-                                            {
-                                                *did_loop.lock().unwrap() = true;
-                                                return false;
-                                            }
-                                            // println!(
-                                            //     "Can continue at {:?} from position {:?}",
-                                            //     guard_position.unwrap(),
-                                            //     start_position
-                                            // );
-                                            let last_path = matrix.guard_path.last().unwrap();
-                                            visit_map.lock().unwrap().insert(
-                                                (
-                                                    direction.clone(),
-                                                    guard_position.unwrap().clone(),
-                                                    obstruction_position.clone(),
-                                                    last_path.start.clone(),
-                                                ),
-                                                true,
-                                            );
-                                            return true;
-                                        }
-                                        None => {
-                                            *did_loop.lock().unwrap() = false;
-                                            false
-                                        }
-                                    },
-                                );
-                            }
+        .filter(|(start_position, direction)| {
+            // creating a new matrix each time so I can use my navigate function without any concerns
+            // side effects, what?! who?
+            let mut matrix = matrices.clone();
+            matrix.guard_path = vec![];
+            // also add the position of the guard in this instant
+            matrix.node_matrix[*start_position].node_type = NodeType::GUARD;
+            if add_obstruction_in_front(&mut matrix, start_position, direction).is_none() {
+                return false;
+            }
+
+            let (direction, guard_position) =
+                matrix.navigate_and_get_direction(direction, start_position);
+            let mut states = HashSet::new();
+            let mut did_loop = false;
+            navigate(
+                matrix,
+                direction.as_ref(),
+                guard_position.as_ref(),
+                |_, guard_position, direction| match (guard_position, direction) {
+                    (Some(guard_position), Some(direction)) => {
+                        // standing on the same cell facing the same way again means a loop
+                        if !states.insert((*guard_position, *direction)) {
+                            did_loop = true;
+                            return false;
                         }
+                        true
                     }
-                    let did_loop = *did_loop.lock().unwrap();
-                    if did_loop {
-                        println!(
-                            "Direction: {:?}\nObstruction: {:?}\nLuard Position: {:?} \n{}",
-                            direction.unwrap().clone().arrow(),
-                            obstruction_position.clone(),
-                            guard_position.clone().unwrap(),
-                            print_path(&matrix, &obstruction_position, &guard_position.unwrap())
-                        );
-                        // println!("Obstruction in position: {:?}", &obstruction_position);
-                    }
-                    remove_obstruction_from_position(&mut matrix, &obstruction_position);
-                    did_loop
-                })
-                .count()
+                    _ => false,
+                },
+            );
+            did_loop
         })
-        .sum();
-    println!("Total obstructions: {}", total_obstructions);
-    assert_eq!(total_obstructions, 6);
+        .count()
 }
 
-pub fn main(_: String) {
-    part_2();
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Matrices;
+
+    fn parse(input: &str) -> Result<Matrices, SolutionError> {
+        Ok(extract_matrices_from_input(input)?)
+    }
+
+    fn part1(matrices: &Matrices) -> Result<Answer, SolutionError> {
+        Ok(part_1(matrices).into())
+    }
+
+    fn part2(matrices: &Matrices) -> Result<Answer, SolutionError> {
+        Ok(part_2(matrices).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use core::fmt;
-    use std::{cell::RefCell, rc::Rc, string};
-
     use super::*;
+    use crate::util::util;
+
+    fn read_matrices(file: &str) -> Matrices {
+        extract_matrices_from_input(&util::read_input("day6", file).unwrap()).unwrap()
+    }
+
     #[test]
     fn test_read_input() {
-        let matrices = extract_matrices_from_input("test.txt");
-        assert_eq!(matrices.visit_matrix.len(), 10);
-        assert_eq!(matrices.visit_matrix[0].len(), 10);
+        let matrices = read_matrices("test.txt");
+        assert_eq!(matrices.visit_matrix.height(), 10);
+        assert_eq!(matrices.visit_matrix.width(), 10);
     }
     #[test]
     fn navigate_test() {
-        let mut matrices = extract_matrices_from_input("test.txt");
+        let mut matrices = read_matrices("test.txt");
         let (direction, guard_position) =
-            matrices.navigate_and_get_direction(&Direction::UP, &matrices.find_guard().unwrap());
+            matrices.navigate_and_get_direction(&Direction4::Up, &matrices.find_guard().unwrap());
         let matrices = navigate(
             matrices,
             direction.as_ref(),
             guard_position.as_ref(),
             |_, _, direction| direction.is_some(),
         );
-        let matrix_string = print_matrix(&matrices.visit_matrix);
+        let matrix_string = matrices.visit_matrix.to_string();
         print!("{}", matrix_string);
         assert_eq!(
             matrix_string,
@@ -704,9 +418,9 @@ mod tests {
     }
     #[test]
     fn navigate_with_test_answer() {
-        let mut matrices = extract_matrices_from_input("test.txt");
+        let mut matrices = read_matrices("test.txt");
         let (direction, guard_position) =
-            matrices.navigate_and_get_direction(&Direction::UP, &matrices.find_guard().unwrap());
+            matrices.navigate_and_get_direction(&Direction4::Up, &matrices.find_guard().unwrap());
         let matrices = navigate(
             matrices,
             direction.as_ref(),
@@ -715,55 +429,39 @@ mod tests {
         );
         let uniques = matrices
             .visit_matrix
-            .iter()
-            .flatten()
-            .filter(|number| number.0.len() > 0)
+            .values()
+            .filter(|number| !number.0.is_empty())
             .count();
         println!("uniques: {}", uniques);
         assert_eq!(uniques, 41);
     }
     #[test]
-    fn navigate_with_real_answer() {
-        let mut matrices = extract_matrices_from_input("input.txt");
-        let (direction, guard_position) =
-            matrices.navigate_and_get_direction(&Direction::UP, &matrices.find_guard().unwrap());
-        let matrices = navigate(
-            matrices,
-            direction.as_ref(),
-            guard_position.as_ref(),
-            |_, _, direction| direction.is_some(),
-        );
-        let matrix_string = print_matrix(&matrices.visit_matrix);
-        print!("{}", matrix_string);
-        let uniques = matrices
-            .visit_matrix
-            .iter()
-            .enumerate()
-            .map(|(x, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter_map(move |(y, col)| match col.0.len() > 0 {
-                        true => Some((
-                            Position {
-                                x: x.clone(),
-                                y: y.clone(),
-                            },
-                            col.clone(),
-                        )),
-                        false => None,
-                    })
-            })
-            .flatten()
-            .count();
-        println!("uniques: {}", uniques);
-        assert_eq!(uniques, 5199);
+    fn navigate_part_2_with_test() {
+        assert_eq!(part_2(&read_matrices("test.txt")), 6);
     }
     #[test]
-    fn navigate_part_2_with_test() {
-        part_2();
+    fn test_parse_errors() {
+        assert!(matches!(
+            extract_matrices_from_input("..^\n.\n"),
+            Err(ParseError::Grid(GridError::Ragged { line: 2, .. }))
+        ));
+        assert!(matches!(
+            extract_matrices_from_input("..\n.#\n"),
+            Err(ParseError::NoGuard)
+        ));
+    }
+    #[test]
+    fn test_guard_facing() {
+        // the guard walks the way its arrow points, not always up
+        for (map, visited) in [("..\n.>\n", 1), ("..\n.<\n", 2), (".v\n..\n", 2)] {
+            let matrices = Day6::parse(map).unwrap();
+            assert_eq!(Day6::part1(&matrices).unwrap(), Answer::Number(visited));
+        }
     }
     #[test]
-    fn navigate_part_2_with_answer() {
-        part_2();
+    fn part_2_counts_each_obstruction_once() {
+        // the first walk crosses itself on this map
+        let matrices = Day6::parse(".#....\n.....#\n#.....\n......\n.^..#.\n......\n").unwrap();
+        assert_eq!(Day6::part2(&matrices).unwrap(), Answer::Number(2));
     }
 }
@@ -1,29 +1,7 @@
-use core::panic;
 use num::BigInt;
-use std::fmt::{Debug, format};
-use std::{
-    ops::{Add, Mul},
-    str::FromStr,
-};
+use std::{error::Error, fmt};
 
-use crate::util::util;
-use itertools::Itertools;
-
-pub fn main(input: &str, part: &str) {
-    match part {
-        "1" => {
-            part1(input);
-        }
-        "2" => part2(input),
-        _ => {
-            println!("day 7 no part selected")
-        }
-    }
-}
-
-fn extract_rules_and_rows_from_input(input: &str) -> String {
-    util::read_input("day7", input)
-}
+use crate::solution::solution::{Answer, Part, Solution, SolutionError};
 
 #[derive(Clone, Copy)]
 enum OPERATOR {
@@ -32,263 +10,169 @@ enum OPERATOR {
 }
 
 fn get_possible_operands() -> Vec<OPERATOR> {
-    return vec![OPERATOR::ADD, OPERATOR::MULTIPLY];
-}
-
-struct Operation<T: Add<Output = T> + Mul<Output = T>> {
-    operator: OPERATOR,
-    first: T,
-    second: T,
+    vec![OPERATOR::ADD, OPERATOR::MULTIPLY]
 }
 
-impl<T: Add<Output = T> + Mul<Output = T> + FromStr + Clone> Operation<T>
-where
-    BigInt: From<T>,
-{
-    fn new_from_string(string_operation: String, operator: OPERATOR) -> Operation<T>
-    where
-        T: FromStr,
-        <T as FromStr>::Err: Debug,
-    {
-        let split_line = string_operation
-            .split(&operator.symbol())
-            .collect::<Vec<_>>();
-        let first = split_line[0];
-        let second = split_line[1];
-        let first_number = first.parse::<T>();
-        let second_number = second.parse::<T>();
-        Operation {
-            operator: operator,
-            first: first_number.expect("error parsing first number"),
-            second: second_number.expect("error parsing second number"),
+impl OPERATOR {
+    fn apply_operation(&self, first: &BigInt, second: &BigInt) -> BigInt {
+        match self {
+            OPERATOR::ADD => first + second,
+            OPERATOR::MULTIPLY => first * second,
         }
     }
-    fn apply_operation(&self) -> BigInt {
-        return self
-            .operator
-            .apply_operation::<T>(self.first.clone(), self.second.clone());
-    }
 }
 
-fn string_to_operator(st: String) -> OPERATOR {
-    match st.as_str() {
-        "+" => OPERATOR::ADD,
-        "*" => OPERATOR::MULTIPLY,
-        _ => panic!(),
-    }
+/// Every value the operands can make, operators are applied left to right.
+fn possible_results(operands: &[BigInt]) -> Vec<BigInt> {
+    let Some((first, rest)) = operands.split_first() else {
+        return vec![];
+    };
+    rest.iter().fold(vec![first.clone()], |results, operand| {
+        results
+            .iter()
+            .flat_map(|result| {
+                get_possible_operands()
+                    .into_iter()
+                    .map(move |operator| operator.apply_operation(result, operand))
+            })
+            .collect()
+    })
 }
 
-impl OPERATOR {
-    fn symbol(&self) -> String {
-        match self {
-            OPERATOR::ADD => "+".to_string(),
-            OPERATOR::MULTIPLY => "*".to_string(),
-        }
-    }
-    fn symbol_byte(&self) -> u8 {
+/// A line of the input, `result: operands...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equation {
+    pub result: BigInt,
+    pub operands: Vec<BigInt>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    MissingColon { line: usize, text: String },
+    NotANumber { line: usize, text: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            OPERATOR::ADD => b'+',
-            OPERATOR::MULTIPLY => b'*',
+            ParseError::MissingColon { line, text } => write!(
+                f,
+                "line {}: equation {:?} should be a result, a colon and the numbers",
+                line, text
+            ),
+            ParseError::NotANumber { line, text } => {
+                write!(f, "line {}: {:?} is not a number", line, text)
+            }
         }
     }
-    fn apply_operation<T: Add<Output = T> + Mul<Output = T>>(&self, first: T, second: T) -> BigInt
-    where
-        BigInt: From<T>,
-    {
-        match self {
-            OPERATOR::ADD => BigInt::from(first).add(BigInt::from(second)),
-            OPERATOR::MULTIPLY => BigInt::from(first).mul(BigInt::from(second)),
+}
+
+impl Error for ParseError {}
+
+fn number(text: &str, line: usize) -> Result<BigInt, ParseError> {
+    text.trim().parse().map_err(|_| ParseError::NotANumber {
+        line,
+        text: text.to_string(),
+    })
+}
+
+/// One equation per line, blank lines are skipped.
+pub fn parse_equations(input: &str) -> Result<Vec<Equation>, ParseError> {
+    let mut equations = vec![];
+    for (index, text) in input.lines().enumerate() {
+        let line = index + 1;
+        if text.trim().is_empty() {
+            continue;
         }
+        let Some((result, operands)) = text.split_once(':') else {
+            return Err(ParseError::MissingColon {
+                line,
+                text: text.to_string(),
+            });
+        };
+        equations.push(Equation {
+            result: number(result, line)?,
+            operands: operands
+                .split_whitespace()
+                .map(|operand| number(operand, line))
+                .collect::<Result<Vec<_>, _>>()?,
+        });
     }
+    Ok(equations)
 }
 
-fn generate_operand_string_list_from_pair(first: String, second: String) -> Vec<String> {
-    get_possible_operands()
+fn part1(equations: &[Equation]) -> BigInt {
+    equations
         .iter()
-        .map(|operand| format!("{}{}{}", first, operand.symbol(), second))
-        .collect()
+        .filter(|equation| possible_results(&equation.operands).contains(&equation.result))
+        .map(|equation| equation.result.clone())
+        .sum()
 }
 
-fn recursive_walk(remainder: Vec<String>, acc: String) -> Vec<String> {
-    if remainder.len() < 2 {
-        return vec![acc];
-    }
+pub struct Day7;
 
-    // if remainder.len() < 2 {
-    //     let first = &remainder[0];
-    //     let equations = get_possible_operands()
-    //         .iter()
-    //         .map(|operator| format!("{}{}{}", acc, operator.symbol(), first).to_string())
-    //         .collect::<Vec<String>>();
-    //     return equations;
-    // }
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const PARTS: &'static [Part] = &[Part::One];
+    type Input = Vec<Equation>;
 
-    let first = &remainder[0];
-    let second = &remainder[1];
-    let reduced_remainder = remainder[2..].to_vec();
-    let equations = get_possible_operands()
-        .iter()
-        .map(|operator| format!("{}{}{}", first, operator.symbol(), second).to_string())
-        .collect::<Vec<String>>();
+    fn parse(input: &str) -> Result<Vec<Equation>, SolutionError> {
+        Ok(parse_equations(input)?)
+    }
 
-    let equations = equations
-        .iter()
-        .map(|new_equation| {
-            let reduced_remainder =
-                vec![vec![new_equation.clone()], reduced_remainder.clone()].concat();
-            recursive_walk(reduced_remainder.to_vec(), new_equation.to_string())
-        })
-        .flatten()
-        .collect::<Vec<_>>();
-    equations
-}
+    fn part1(equations: &Vec<Equation>) -> Result<Answer, SolutionError> {
+        Ok(part1(equations).into())
+    }
 
-fn generate_equations_for_line(line: String) -> Vec<String> {
-    let split_equation = line
-        .split_whitespace()
-        .map(String::from)
-        .collect::<Vec<String>>();
-    recursive_walk(split_equation, "".to_string())
+    fn part2(_: &Vec<Equation>) -> Result<Answer, SolutionError> {
+        Ok(Answer::Unsolved)
+    }
 }
 
-// fn recursive_permutation(numbers: Vec<String>) -> Vec<String> {
-//     if numbers.len() == 1 {
-//         return numbers.clone();
-//     }
-//     let number_pairs = numbers.into_iter().permutations(2);
-// 	numbers.iter().multi_cartesian_product();
-//     number_pairs.map(|pair| {
-//         // In my mind, we only need one iteration for the permutations.
-//         // and we shouldn't resolve it, we only need to create the string
-//         let composed_operations = get_possible_operands().iter().map(|op| {
-// 			let first = pair[0];
-// 			let second = pair[1];
-// 			format!("{}{}{}", first, op.symbol(), second)
-// 		}).collect::<Vec<_>>();
-// 		composed_operations.iter().map(|composed_operation|{
-// 			recursive_permutation(numbers)
-// 		});
-//     })
-//     // Then, I have to apply a different operation between each window
-// }
-
-// number_pairs.map(|pair| {
-// 	let first: i32 = pair[0].parse().unwrap();
-// 	let second: i32 = pair[1].parse().unwrap();
-// 	// I can apply any operation to this pair, will store it as an array
-// 	get_operations().iter().map(|op| op.apply_operation(first, second)).collect::<Vec<_>>()
-// });
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1_counts_single_matches() {
+        // 10 * 19 is the only way to make 190, 3267 can be made two ways
+        let input = Day7::parse("190: 10 19\n3267: 81 40 27\n").unwrap();
+        assert_eq!(
+            Day7::part1(&input).unwrap(),
+            Answer::from(BigInt::from(3457))
+        );
 
-fn walk_and_resolve_equation(line: String) -> BigInt {
-    let bytes = line.as_bytes();
-    let mut first_operand = None;
-    let mut second_operand = None;
-    let mut operator: Option<OPERATOR> = None;
-    let mut acc = BigInt::from(0);
-    for byte in bytes {
-        match byte {
-            b'+' | b'*' => match operator {
-                Some(old_operator) => {
-                    let operation = format!(
-                        "{}{}{}",
-                        first_operand.clone().unwrap(),
-                        old_operator.symbol(),
-                        second_operand.clone().unwrap()
-                    );
-                    let op = Operation::<BigInt>::new_from_string(operation, old_operator);
-                    acc = op.apply_operation();
-                    first_operand = Some(acc.to_string());
-                    second_operand = None;
-                    operator = Some(string_to_operator(String::from_utf8(vec![*byte]).unwrap()));
-                }
-                None => {
-                    operator = Some(string_to_operator(String::from_utf8(vec![*byte]).unwrap()));
-                }
-            },
-            _ => match first_operand {
-                Some(ref first) => match operator {
-                    Some(operator) => match second_operand {
-                        Some(second) => {
-                            second_operand = Some(format!(
-                                "{}{}",
-                                second,
-                                String::from_utf8(vec![byte.clone()]).unwrap()
-                            ))
-                        }
-                        None => {
-                            second_operand = Some(String::from_utf8(vec![byte.clone()]).unwrap())
-                        }
-                    },
-                    None => {
-                        first_operand = Some(format!(
-                            "{}{}",
-                            first,
-                            String::from_utf8(vec![byte.clone()]).unwrap()
-                        ))
-                    }
-                },
-                None => {
-                    first_operand = Some(format!(
-                        "{}",
-                        String::from_utf8(vec![byte.clone()]).unwrap()
-                    ))
-                }
-            },
-        }
-    }
-    if first_operand.is_some() && second_operand.is_some() && operator.is_some() {
-        let operation = format!(
-            "{}{}{}",
-            first_operand.clone().unwrap(),
-            operator.unwrap().symbol(),
-            second_operand.clone().unwrap()
+        let unsolvable = Day7::parse("5: 1 1\n").unwrap();
+        assert_eq!(
+            Day7::part1(&unsolvable).unwrap(),
+            Answer::from(BigInt::from(0))
         );
-        let op = Operation::<BigInt>::new_from_string(operation, operator.unwrap());
-        acc = op.apply_operation();
+
+        // a single operand needs no operator, it only has to equal the result
+        let single = Day7::parse("5: 5\n6: 5\n").unwrap();
+        assert_eq!(Day7::part1(&single).unwrap(), Answer::from(BigInt::from(5)));
     }
-    acc
-}
 
-fn part1(input: &str) {
-    let file = extract_rules_and_rows_from_input(input);
-    let lines = file.lines();
-    let lines_that_match: BigInt = lines
-        .filter_map(|line| {
-            let split = line.split(":").collect::<Vec<_>>();
-            let result = split[0].to_string();
-            let operation = split[1].to_string();
-            let ops = generate_equations_for_line(operation.to_string());
-            println!(
-                "==============================================================================="
-            );
-            println!("There are this many options: {}", ops.len());
-            let results_that_match = ops
-                .iter()
-                .filter_map(|op| {
-                    let parsed_result = result.parse::<BigInt>().unwrap();
-                    let resolved_equation = walk_and_resolve_equation(op.to_string());
-                    return if parsed_result == resolved_equation {
-                        Some(resolved_equation)
-                    } else {
-                        None
-                    };
-                })
-                .collect::<Vec<_>>();
-            results_that_match.iter().for_each(|op| println!("{}", op));
-            println!(
-                "Result {} is matched by this many lines {}",
-                result,
-                results_that_match.len()
-            );
-            if results_that_match.len() > 1 {
-                Some(result.parse::<BigInt>().unwrap())
-            } else {
-                None
+    #[test]
+    fn test_parse_equations() {
+        assert_eq!(
+            parse_equations("190: 10 19\n\n").unwrap(),
+            vec![Equation {
+                result: BigInt::from(190),
+                operands: vec![BigInt::from(10), BigInt::from(19)],
+            }]
+        );
+        let error = parse_equations("190: 10 19\n83 17\n").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::MissingColon {
+                line: 2,
+                text: "83 17".to_string()
             }
-        })
-        .reduce(|acc, curr| acc.add(curr))
-        .unwrap();
-    println!("lines that match: {}", lines_that_match)
+        );
+        assert_eq!(
+            parse_equations("5: 1 x\n").unwrap_err().to_string(),
+            "line 1: \"x\" is not a number"
+        );
+    }
 }
-
-fn part2(input: &str) {}
@@ -1 +1 @@
-pub mod day7;
\ No newline at end of file
+pub mod day7;
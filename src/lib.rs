@@ -1,5 +1,11 @@
+#![allow(non_snake_case)]
+#![allow(clippy::module_inception)]
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod cli;
 pub mod day4;
 pub mod day5;
 pub mod day6;
 pub mod day7;
+pub mod solution;
 pub mod util;
@@ -0,0 +1,242 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    solution::{
+        registry,
+        solution::{Answer, Part, RunError},
+    },
+    util::util::{InputConfig, InputError},
+};
+
+pub const MANIFEST: &str = "answers.toml";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedAnswer {
+    pub day: String,
+    pub input: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+impl fmt::Display for ExpectedAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} part {}", self.day, self.input, self.part)
+    }
+}
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Unreadable { path: PathBuf, source: io::Error },
+    Syntax(String),
+    Invalid(String),
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::Unreadable { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            ManifestError::Syntax(message) => write!(f, "invalid answers manifest: {}", message),
+            ManifestError::Invalid(message) => write!(f, "invalid answers manifest: {}", message),
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+pub fn manifest_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(MANIFEST)
+}
+
+fn parse_part(key: &str) -> Option<Part> {
+    match key {
+        "part1" => Some(Part::One),
+        "part2" => Some(Part::Two),
+        _ => None,
+    }
+}
+
+fn answer_of(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::Integer(number) => Some(number.to_string()),
+        // answers that do not fit an i64 are written as strings
+        toml::Value::String(text) => Some(text.clone()),
+        _ => None,
+    }
+}
+
+/// Reads a manifest laid out as `[<day>."<input file>"]` tables holding `part1`/`part2` keys.
+pub fn parse_manifest(text: &str) -> Result<Vec<ExpectedAnswer>, ManifestError> {
+    let table = text
+        .parse::<toml::Table>()
+        .map_err(|error| ManifestError::Syntax(error.to_string()))?;
+    let mut expected = vec![];
+    for (day, inputs) in &table {
+        if registry::find_by_name(day).is_none() {
+            return Err(ManifestError::Invalid(format!("unknown day `{}`", day)));
+        }
+        let inputs = inputs.as_table().ok_or_else(|| {
+            ManifestError::Invalid(format!("`{}` should map input files to answers", day))
+        })?;
+        for (input, parts) in inputs {
+            let parts = parts.as_table().ok_or_else(|| {
+                ManifestError::Invalid(format!("`{}.{}` should hold part answers", day, input))
+            })?;
+            for (key, value) in parts {
+                let part = parse_part(key).ok_or_else(|| {
+                    ManifestError::Invalid(format!("unknown part `{}` in `{}.{}`", key, day, input))
+                })?;
+                let answer = answer_of(value).ok_or_else(|| {
+                    ManifestError::Invalid(format!(
+                        "`{}.{}.{}` should be a number or a string",
+                        day, input, key
+                    ))
+                })?;
+                expected.push(ExpectedAnswer {
+                    day: day.clone(),
+                    input: input.clone(),
+                    part,
+                    answer,
+                });
+            }
+        }
+    }
+    Ok(expected)
+}
+
+pub fn load_manifest(path: &Path) -> Result<Vec<ExpectedAnswer>, ManifestError> {
+    let text = fs::read_to_string(path).map_err(|source| ManifestError::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_manifest(&text)
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Correct,
+    Wrong(Answer),
+    /// The input is there but the day rejected it.
+    Failed(RunError),
+    /// The input is not on disk, puzzle inputs are private so this is not a failure.
+    Skipped(InputError),
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub expected: ExpectedAnswer,
+    pub outcome: Outcome,
+}
+
+pub fn verify(expected: &[ExpectedAnswer], config: &InputConfig) -> Vec<Verification> {
+    expected
+        .iter()
+        .map(|expected| {
+            let solution = registry::find_by_name(&expected.day)
+                .expect("manifest days are checked when it is parsed");
+            let outcome = match solution.run_file_with(config, &expected.input, expected.part) {
+                Ok(answer) if answer.to_string() == expected.answer => Outcome::Correct,
+                Ok(answer) => Outcome::Wrong(answer),
+                Err(RunError::Input(error)) => Outcome::Skipped(error),
+                Err(error) => Outcome::Failed(error),
+            };
+            Verification {
+                expected: expected.clone(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let expected = parse_manifest(
+            r#"
+            [day7."test.txt"]
+            part1 = 3749
+            part2 = "11387"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(expected.len(), 2);
+        assert_eq!(expected[0].day, "day7");
+        assert_eq!(expected[0].input, "test.txt");
+        assert_eq!(expected[0].part, Part::One);
+        assert_eq!(expected[1].answer, "11387");
+
+        for invalid in [
+            "[day99.\"test.txt\"]\npart1 = 1",
+            "[day4.\"test.txt\"]\npart3 = 1",
+            "[day4.\"test.txt\"]\npart1 = 1.5",
+            "[day4",
+        ] {
+            assert!(
+                parse_manifest(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrong_and_skipped_answers() {
+        let expected = parse_manifest(
+            r#"
+            [day4."test.txt"]
+            part1 = 17
+            [day4."missing.txt"]
+            part1 = 18
+            "#,
+        )
+        .unwrap();
+        let verifications = verify(&expected, &InputConfig::default());
+        let outcome_of = |input: &str| {
+            &verifications
+                .iter()
+                .find(|verification| verification.expected.input == input)
+                .unwrap()
+                .outcome
+        };
+        assert!(matches!(
+            outcome_of("test.txt"),
+            Outcome::Wrong(Answer::Number(18))
+        ));
+        assert!(matches!(outcome_of("missing.txt"), Outcome::Skipped(_)));
+
+        // an input that is there but does not parse fails instead of being skipped
+        let directory = std::env::temp_dir().join("aoc_2024_invalid_answers");
+        fs::create_dir_all(directory.join("day5")).unwrap();
+        fs::write(directory.join("day5").join("invalid.txt"), "1|2\n2|x\n").unwrap();
+        let expected = parse_manifest("[day5.\"invalid.txt\"]\npart1 = 1").unwrap();
+        let verifications = verify(&expected, &InputConfig::new(Some(directory)));
+        assert!(matches!(
+            verifications[0].outcome,
+            Outcome::Failed(RunError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_answers_manifest() {
+        let expected = load_manifest(&manifest_path()).unwrap();
+        let wrong = verify(&expected, &InputConfig::from_env())
+            .into_iter()
+            .filter_map(|verification| match verification.outcome {
+                Outcome::Wrong(answer) => Some(format!(
+                    "{}: expected {}, got {}",
+                    verification.expected, verification.expected.answer, answer
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(wrong.is_empty(), "wrong answers:\n{}", wrong.join("\n"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod answers;
+pub mod registry;
+pub mod solution;
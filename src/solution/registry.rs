@@ -0,0 +1,35 @@
+use crate::{
+    day4::day4::Day4, day5::day5::Day5, day6::day6::Day6, day7::day7::Day7,
+    solution::solution::Runner,
+};
+
+pub static SOLUTIONS: &[&dyn Runner] = &[&Day4, &Day5, &Day6, &Day7];
+
+pub fn find(day: u8) -> Option<&'static dyn Runner> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|solution| solution.day() == day)
+}
+
+/// Days with at least one part, a freshly scaffolded day has none and no input to run yet.
+pub fn implemented() -> Vec<&'static dyn Runner> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .filter(|solution| !solution.parts().is_empty())
+        .collect()
+}
+
+/// Accepts both `7` and `day7`, whether or not that day is solved yet.
+pub fn day_number(day: &str) -> Option<u8> {
+    let number = day.strip_prefix("day").unwrap_or(day);
+    number
+        .parse::<u8>()
+        .ok()
+        .filter(|number| (1..=25).contains(number))
+}
+
+pub fn find_by_name(day: &str) -> Option<&'static dyn Runner> {
+    day_number(day).and_then(find)
+}
@@ -0,0 +1,183 @@
+use std::{any::Any, error::Error, fmt};
+
+use num::BigInt;
+
+use crate::util::util::{InputConfig, InputError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.number().fmt(f)
+    }
+}
+
+/// The answer of a part, kept as a value so it can be compared or stored instead of printed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Big(BigInt),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        Answer::Big(value)
+    }
+}
+
+/// Why a day could not handle its input, every day keeps its own error type behind it.
+pub type SolutionError = Box<dyn Error + Send + Sync>;
+
+#[derive(Debug)]
+pub enum RunError {
+    Input(InputError),
+    Parse(SolutionError),
+    Solve(Part, SolutionError),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Input(error) => write!(f, "{}", error),
+            RunError::Parse(error) => write!(f, "invalid input: {}", error),
+            RunError::Solve(part, error) => write!(f, "part {} failed: {}", part, error),
+        }
+    }
+}
+
+impl Error for RunError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RunError::Input(error) => Some(error),
+            RunError::Parse(error) | RunError::Solve(_, error) => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<InputError> for RunError {
+    fn from(error: InputError) -> Self {
+        RunError::Input(error)
+    }
+}
+
+/// A day of the calendar. `parse` runs once per input and both parts work on the parsed value.
+pub trait Solution {
+    const DAY: u8;
+    /// Parts that have an implementation, the others answer [`Answer::Unsolved`].
+    const PARTS: &'static [Part] = &Part::ALL;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, SolutionError>;
+    fn part1(input: &Self::Input) -> Result<Answer, SolutionError>;
+    fn part2(input: &Self::Input) -> Result<Answer, SolutionError>;
+}
+
+/// Type erased view of a [`Solution`] so every day can live in the same registry.
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+    fn parts(&self) -> &'static [Part];
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>, SolutionError>;
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, SolutionError>;
+
+    fn name(&self) -> String {
+        format!("day{}", self.day())
+    }
+
+    fn run(&self, input: &str, part: Part) -> Result<Answer, RunError> {
+        let parsed = self.parse_input(input).map_err(RunError::Parse)?;
+        self.solve(parsed.as_ref(), part)
+            .map_err(|error| RunError::Solve(part, error))
+    }
+
+    fn run_file(&self, file: &str, part: Part) -> Result<Answer, RunError> {
+        self.run_file_with(&InputConfig::from_env(), file, part)
+    }
+
+    fn run_file_with(
+        &self,
+        config: &InputConfig,
+        file: &str,
+        part: Part,
+    ) -> Result<Answer, RunError> {
+        let input = config.read(&self.name(), file)?;
+        self.run(&input, part)
+    }
+}
+
+impl<S> Runner for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>, SolutionError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, SolutionError> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input was parsed by another day");
+        match part {
+            Part::One => S::part1(input),
+            Part::Two => S::part2(input),
+        }
+    }
+}
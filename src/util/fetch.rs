@@ -0,0 +1,260 @@
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::util::util::InputConfig;
+
+/// Environment variable holding the `session` cookie of a logged in adventofcode.com account.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// File holding the session token when the environment variable is not set, kept out of git.
+pub const SESSION_FILE: &str = ".aoc-session";
+pub const INPUT_FILE: &str = "input.txt";
+
+const BASE_URL: &str = "https://adventofcode.com/2024";
+const USER_AGENT: &str = "Advent_of_Code_2024 input fetcher (ureq)";
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession { tried: Vec<PathBuf> },
+    Status { url: String, code: u16 },
+    Network { url: String, message: String },
+    Write { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession { tried } => {
+                write!(
+                    f,
+                    "no session token, set {} or write it to one of:",
+                    SESSION_VAR
+                )?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            FetchError::Status { url, code } => {
+                write!(f, "{} answered with status {}", url, code)?;
+                match code {
+                    400 | 401 | 403 => write!(f, ", the session token may have expired"),
+                    404 => write!(f, ", the puzzle may not be unlocked yet"),
+                    _ => Ok(()),
+                }
+            }
+            FetchError::Network { url, message } => {
+                write!(f, "could not download {}: {}", url, message)
+            }
+            FetchError::Write { path, source } => {
+                write!(f, "could not write {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Write { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The HTTP layer of the fetcher, so tests can answer requests without touching the network.
+pub trait HttpClient {
+    fn get(&self, url: &str, session: &str) -> Result<String, FetchError>;
+}
+
+pub struct UreqClient;
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> Result<String, FetchError> {
+        let network = |error: ureq::Error| match error {
+            ureq::Error::StatusCode(code) => FetchError::Status {
+                url: url.to_string(),
+                code,
+            },
+            error => FetchError::Network {
+                url: url.to_string(),
+                message: error.to_string(),
+            },
+        };
+        ureq::get(url)
+            .header("Cookie", &format!("session={}", session))
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .map_err(network)?
+            .body_mut()
+            .read_to_string()
+            .map_err(network)
+    }
+}
+
+pub fn input_url(day: u8) -> String {
+    format!("{}/day/{}/input", BASE_URL, day)
+}
+
+/// `.aoc-session` in the working directory and the crate root, then `~/.config/aoc/session`.
+pub fn session_paths() -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(SESSION_FILE),
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(SESSION_FILE),
+    ];
+    if let Some(home) = env::var_os("HOME") {
+        paths.push(Path::new(&home).join(".config").join("aoc").join("session"));
+    }
+    paths
+}
+
+fn session_from(variable: Option<String>, paths: &[PathBuf]) -> Result<String, FetchError> {
+    let from_files = paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok());
+    variable
+        .into_iter()
+        .chain(from_files)
+        .map(|session| session.trim().to_string())
+        .find(|session| !session.is_empty())
+        .ok_or_else(|| FetchError::NoSession {
+            tried: paths.to_vec(),
+        })
+}
+
+pub fn read_session() -> Result<String, FetchError> {
+    session_from(env::var(SESSION_VAR).ok(), &session_paths())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads the input of `day` unless `read_input` can already find one, an empty file left
+/// by `new` does not count. The session is only asked for when a download is needed.
+pub fn fetch_input(
+    client: &impl HttpClient,
+    config: &InputConfig,
+    day: u8,
+    session: impl FnOnce() -> Result<String, FetchError>,
+) -> Result<Fetched, FetchError> {
+    let name = format!("day{}", day);
+    if let Some(cached) = config
+        .candidate_paths(&name, INPUT_FILE)
+        .into_iter()
+        .find(|path| fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0))
+    {
+        return Ok(Fetched::Cached(cached));
+    }
+    let body = client.get(&input_url(day), &session()?)?;
+
+    let path = config.download_path(&name, INPUT_FILE);
+    let write = |source| FetchError::Write {
+        path: path.clone(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write)?;
+    }
+    // a download that fails half way must not be mistaken for a cached input
+    let partial = path.with_extension("part");
+    fs::write(&partial, body).map_err(write)?;
+    fs::rename(&partial, &path).map_err(write)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    struct FakeClient {
+        response: Result<String, u16>,
+        requests: RefCell<Vec<(String, String)>>,
+    }
+
+    impl FakeClient {
+        fn new(response: Result<&str, u16>) -> FakeClient {
+            FakeClient {
+                response: response.map(String::from),
+                requests: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl HttpClient for FakeClient {
+        fn get(&self, url: &str, session: &str) -> Result<String, FetchError> {
+            self.requests
+                .borrow_mut()
+                .push((url.to_string(), session.to_string()));
+            self.response.clone().map_err(|code| FetchError::Status {
+                url: url.to_string(),
+                code,
+            })
+        }
+    }
+
+    fn empty_input_dir(name: &str) -> InputConfig {
+        let directory = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&directory);
+        InputConfig::new(Some(directory))
+    }
+
+    #[test]
+    fn test_fetch_downloads_once() {
+        let config = empty_input_dir("aoc_2024_fetch");
+        let path = config.download_path("day24", INPUT_FILE);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "").unwrap();
+        let client = FakeClient::new(Ok("1 2 3\n"));
+        let fetched = fetch_input(&client, &config, 24, || Ok("cookie".to_string())).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(config.read("day24", INPUT_FILE).unwrap(), "1 2 3\n");
+        assert_eq!(
+            client.requests.borrow().as_slice(),
+            [(input_url(24), "cookie".to_string())]
+        );
+
+        // the cached file is used without a session or a request
+        let fetched = fetch_input(&client, &config, 24, || panic!("session needed")).unwrap();
+        assert_eq!(fetched, Fetched::Cached(path));
+        assert_eq!(client.requests.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_failed_fetch_writes_nothing() {
+        let config = empty_input_dir("aoc_2024_fetch_failed");
+        let client = FakeClient::new(Err(400));
+        let error = fetch_input(&client, &config, 23, || Ok("expired".to_string())).unwrap_err();
+        assert!(matches!(error, FetchError::Status { code: 400, .. }));
+        assert!(error.to_string().contains("expired"));
+        assert!(!config.download_path("day23", INPUT_FILE).exists());
+
+        let missing = [std::env::temp_dir().join("aoc_2024_no_session")];
+        let error = fetch_input(&client, &config, 23, || session_from(None, &missing)).unwrap_err();
+        assert!(matches!(error, FetchError::NoSession { .. }));
+    }
+
+    #[test]
+    fn test_session_sources() {
+        let file = std::env::temp_dir().join("aoc_2024_session");
+        fs::write(&file, "from-file\n").unwrap();
+        let paths = [file.with_extension("missing"), file];
+        assert_eq!(
+            session_from(Some(" token \n".to_string()), &paths).unwrap(),
+            "token"
+        );
+        assert_eq!(session_from(None, &paths).unwrap(), "from-file");
+        assert_eq!(
+            session_from(Some("".to_string()), &paths).unwrap(),
+            "from-file"
+        );
+        assert!(session_from(None, &paths[..1]).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A point on the screen: `x` is the column and grows to the right, `y` is the row and grows
+/// downwards, so [`Direction4::Up`] is `y - 1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(&self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Moves needed by a king, diagonal steps count as one.
+    pub fn chebyshev(&self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Quarter turn clockwise around the origin, as seen on the screen.
+    pub fn rotate_right(&self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    pub fn rotate_left(&self) -> Point {
+        Point::new(self.y, -self.x)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// Clockwise from up.
+    pub const ALL: [Direction4; 4] = [
+        Direction4::Up,
+        Direction4::Right,
+        Direction4::Down,
+        Direction4::Left,
+    ];
+
+    pub fn delta(&self) -> Point {
+        match self {
+            Direction4::Up => Point::new(0, -1),
+            Direction4::Right => Point::new(1, 0),
+            Direction4::Down => Point::new(0, 1),
+            Direction4::Left => Point::new(-1, 0),
+        }
+    }
+
+    fn turn(&self, quarters: usize) -> Direction4 {
+        Direction4::ALL[(*self as usize + quarters) % 4]
+    }
+
+    pub fn turn_right(&self) -> Direction4 {
+        self.turn(1)
+    }
+
+    pub fn turn_left(&self) -> Direction4 {
+        self.turn(3)
+    }
+
+    pub fn opposite(&self) -> Direction4 {
+        self.turn(2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise from up.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub const DIAGONALS: [Direction8; 4] = [
+        Direction8::UpRight,
+        Direction8::DownRight,
+        Direction8::DownLeft,
+        Direction8::UpLeft,
+    ];
+
+    pub fn delta(&self) -> Point {
+        match self {
+            Direction8::Up => Point::new(0, -1),
+            Direction8::UpRight => Point::new(1, -1),
+            Direction8::Right => Point::new(1, 0),
+            Direction8::DownRight => Point::new(1, 1),
+            Direction8::Down => Point::new(0, 1),
+            Direction8::DownLeft => Point::new(-1, 1),
+            Direction8::Left => Point::new(-1, 0),
+            Direction8::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    fn turn(&self, eighths: usize) -> Direction8 {
+        Direction8::ALL[(*self as usize + eighths) % 8]
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn turn_right(&self) -> Direction8 {
+        self.turn(1)
+    }
+
+    pub fn turn_left(&self) -> Direction8 {
+        self.turn(7)
+    }
+
+    pub fn opposite(&self) -> Direction8 {
+        self.turn(4)
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic_and_distances() {
+        let mut point = Point::new(2, 3);
+        assert_eq!(point + Point::new(1, -1), Point::new(3, 2));
+        assert_eq!(point - Point::new(2, 3), Point::ORIGIN);
+        assert_eq!(point * 3, Point::new(6, 9));
+        assert_eq!(-point, Point::new(-2, -3));
+        point += Direction4::Up.delta();
+        assert_eq!(point, Point::new(2, 2));
+        point -= Point::new(1, 1);
+        assert_eq!(point, Point::new(1, 1));
+
+        assert_eq!(Point::new(1, 1).manhattan(Point::new(4, -3)), 7);
+        assert_eq!(Point::new(1, 1).chebyshev(Point::new(4, -3)), 4);
+    }
+
+    #[test]
+    fn test_turns_match_deltas() {
+        for direction in Direction4::ALL {
+            assert_eq!(
+                direction.turn_right().delta(),
+                direction.delta().rotate_right()
+            );
+            assert_eq!(
+                direction.turn_left().delta(),
+                direction.delta().rotate_left()
+            );
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(Direction8::from(direction).delta(), direction.delta());
+        }
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Left.turn_right(), Direction4::Up);
+
+        for direction in Direction8::ALL {
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(
+                direction.turn_right().turn_right().delta(),
+                direction.delta().rotate_right()
+            );
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    error::Error,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::util::geometry::{Direction4, Direction8, Point};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// Line numbers start at 1, like an editor shows them.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "the grid has no rows"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {} cells, expected {} like the first line",
+                line, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// A rectangular grid stored row by row in one `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Point) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x as i32, y as i32)))
+            .map(&mut cell)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from one line per row, every line must have as many chars as the first.
+    /// Empty lines at the end are ignored.
+    pub fn parse(text: &str, mut cell: impl FnMut(char) -> T) -> Result<Grid<T>, GridError> {
+        let lines = text.lines().collect::<Vec<_>>();
+        let rows = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |last| last + 1);
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (index, line) in lines[..rows].iter().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let found = cells.len() - before;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::Ragged {
+                        line: index + 1,
+                        expected,
+                        found,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Point) -> bool {
+        position.x >= 0
+            && position.y >= 0
+            && (position.x as usize) < self.width
+            && (position.y as usize) < self.height
+    }
+
+    fn index_of(&self, position: Point) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y as usize * self.width + position.x as usize)
+    }
+
+    pub fn get(&self, position: Point) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// `height` rows, empty ones when the grid has no columns.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.cells.len())
+            .map(move |index| Point::new((index % width) as i32, (index / width) as i32))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(position, _)| position)
+    }
+
+    fn neighbors(
+        &self,
+        position: Point,
+        deltas: impl Iterator<Item = Point>,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        deltas.filter_map(move |delta| {
+            let neighbor = position + delta;
+            self.get(neighbor).map(|value| (neighbor, value))
+        })
+    }
+
+    /// Up, right, down and left of `position`, leaving out the ones outside the grid.
+    pub fn neighbors4(&self, position: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(position, Direction4::ALL.iter().map(Direction4::delta))
+    }
+
+    /// The 4-way neighbors and the diagonals, clockwise from up.
+    pub fn neighbors8(&self, position: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(position, Direction8::ALL.iter().map(Direction8::delta))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Point, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter()
+                .map(|(position, value)| f(position, value))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T: fmt::Display> Grid<T> {
+    /// One line per row with `separator` between the cells.
+    pub fn render(&self, separator: &str) -> String {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(separator)
+                    + "\n"
+            })
+            .collect()
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, position: Point) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert_eq!(grid.render(" "), "a b c\nd e f\n");
+
+        assert_eq!(
+            Grid::parse("ab\nc\n", |c| c),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(Grid::parse("", |c| c), Err(GridError::Empty));
+
+        // trailing blank lines are not rows, a blank line between rows still is
+        assert_eq!(Grid::parse("abc\ndef\n\n\n", |c| c), Ok(grid));
+        assert_eq!(Grid::parse("\n\n", |c| c), Err(GridError::Empty));
+        assert_eq!(
+            Grid::parse("ab\n\nab\n", |c| c),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_rows_columns_and_mutation() {
+        let mut grid = sample();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.find(|c| *c == 'e'), Some(Point::new(1, 1)));
+
+        *grid.get_mut(Point::new(0, 0)).unwrap() = 'z';
+        grid[Point::new(2, 1)] = 'y';
+        assert_eq!(grid.to_string(), "zbc\ndey\n");
+
+        let positions = grid.map(|position, _| position.x + position.y);
+        assert_eq!(positions.to_string(), "012\n123\n");
+        assert_eq!(
+            Grid::from_fn(2, 1, |position| position.x),
+            Grid::new(2, 1, 0).map(|p, _| p.x)
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = sample();
+        let corner = grid
+            .neighbors4(Point::new(0, 0))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        assert_eq!(corner, "bd");
+        let middle = grid
+            .neighbors8(Point::new(1, 0))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        assert_eq!(middle, "cfeda");
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let grid = Grid::new(0, 2, 'a');
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[], []]);
+        assert_eq!(grid.render(" "), "\n\n");
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(Grid::from_fn(0, 3, |_| 0).map(|_, x| *x).rows().count(), 3);
+    }
+}
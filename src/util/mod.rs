@@ -1 +1,4 @@
+pub mod fetch;
+pub mod geometry;
+pub mod grid;
 pub mod util;
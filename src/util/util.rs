@@ -1,5 +1,227 @@
-use std::fs;
+use std::{
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
 
-pub fn read_input(day: &str, file: &str) -> String {
-    fs::read_to_string(format!("src/{}/{}", day, file)).unwrap()
+/// Environment variable that points to a directory laid out as `<dir>/<day>/<file>`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+/// File name that makes the loader read the puzzle input from stdin.
+pub const STDIN: &str = "-";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { tried: Vec<PathBuf> },
+    Unreadable { path: PathBuf, source: io::Error },
+    Empty { path: PathBuf },
+    Encoding { path: PathBuf, valid_up_to: usize },
+}
+
+fn describe(path: &Path) -> String {
+    if path == Path::new(STDIN) {
+        "stdin".to_string()
+    } else {
+        path.display().to_string()
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { tried } => {
+                write!(f, "input file not found, tried:")?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Unreadable { path, source } => {
+                write!(f, "could not read {}: {}", describe(path), source)
+            }
+            InputError::Empty { path } => write!(f, "input {} is empty", describe(path)),
+            InputError::Encoding { path, valid_up_to } => write!(
+                f,
+                "input {} is not valid UTF-8 after byte {}",
+                describe(path),
+                valid_up_to
+            ),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Unreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Where puzzle inputs are looked up. Without an input directory they are read
+/// from `src/<day>/<file>`, as they always were.
+#[derive(Clone, Debug, Default)]
+pub struct InputConfig {
+    pub input_dir: Option<PathBuf>,
+}
+
+impl InputConfig {
+    pub fn new(input_dir: Option<PathBuf>) -> InputConfig {
+        InputConfig { input_dir }
+    }
+
+    pub fn from_env() -> InputConfig {
+        InputConfig::new(env::var_os(INPUT_DIR_VAR).map(PathBuf::from))
+    }
+
+    /// Places where `file` may live: inside the input directory when there is one, then
+    /// `src/<day>` relative to the working directory and relative to the crate root.
+    pub fn candidate_paths(&self, day: &str, file: &str) -> Vec<PathBuf> {
+        if Path::new(file).is_absolute() {
+            return vec![PathBuf::from(file)];
+        }
+        let relative = Path::new("src").join(day).join(file);
+        let from_crate = Path::new(env!("CARGO_MANIFEST_DIR")).join(&relative);
+        let mut paths = vec![];
+        if let Some(input_dir) = &self.input_dir {
+            paths.push(input_dir.join(day).join(file));
+        }
+        for path in [relative, from_crate] {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    /// Where a downloaded input is stored: inside the input directory when there is one,
+    /// otherwise next to the solution in `src/<day>`.
+    pub fn download_path(&self, day: &str, file: &str) -> PathBuf {
+        match &self.input_dir {
+            Some(input_dir) => input_dir.join(day).join(file),
+            None => Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src")
+                .join(day)
+                .join(file),
+        }
+    }
+
+    pub fn read(&self, day: &str, file: &str) -> Result<String, InputError> {
+        if file == STDIN {
+            return read_from(io::stdin().lock(), Path::new(STDIN));
+        }
+        let tried = self.candidate_paths(day, file);
+        match tried.iter().find(|path| path.is_file()) {
+            Some(path) => read_path(path),
+            None => Err(InputError::Missing { tried }),
+        }
+    }
+}
+
+fn read_from(mut reader: impl Read, path: &Path) -> Result<String, InputError> {
+    let mut bytes = vec![];
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|source| InputError::Unreadable {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    let text = String::from_utf8(bytes).map_err(|error| InputError::Encoding {
+        path: path.to_path_buf(),
+        valid_up_to: error.utf8_error().valid_up_to(),
+    })?;
+    if text.trim().is_empty() {
+        return Err(InputError::Empty {
+            path: path.to_path_buf(),
+        });
+    }
+    Ok(text)
+}
+
+fn read_path(path: &Path) -> Result<String, InputError> {
+    let file = fs::File::open(path).map_err(|source| InputError::Unreadable {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    read_from(file, path)
+}
+
+pub fn read_input(day: &str, file: &str) -> Result<String, InputError> {
+    InputConfig::from_env().read(day, file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_existing_input() {
+        let input = read_input("day4", "test.txt").unwrap();
+        assert!(input.starts_with("MMMSXXMASM"));
+    }
+
+    #[test]
+    fn test_missing_input_lists_paths() {
+        match read_input("day4", "does_not_exist.txt") {
+            Err(InputError::Missing { tried }) => {
+                assert!(!tried.is_empty());
+                assert!(
+                    tried
+                        .iter()
+                        .all(|path| path.ends_with("src/day4/does_not_exist.txt"))
+                );
+            }
+            other => panic!("expected a missing input error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_empty_and_non_utf8_input() {
+        let directory = std::env::temp_dir().join("aoc_2024_util_tests");
+        fs::create_dir_all(&directory).unwrap();
+        let empty = directory.join("empty.txt");
+        fs::write(&empty, "\n").unwrap();
+        assert!(matches!(read_path(&empty), Err(InputError::Empty { .. })));
+
+        let binary = directory.join("binary.txt");
+        fs::write(&binary, [b'X', b'M', 0xff]).unwrap();
+        assert!(matches!(
+            read_path(&binary),
+            Err(InputError::Encoding { valid_up_to: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_input_dir_and_absolute_paths() {
+        let directory = std::env::temp_dir().join("aoc_2024_input_dir");
+        fs::create_dir_all(directory.join("day4")).unwrap();
+        let generated = directory.join("day4").join("generated.txt");
+        fs::write(&generated, "XMAS\n").unwrap();
+
+        let config = InputConfig::new(Some(directory.clone()));
+        assert_eq!(
+            config.candidate_paths("day4", "generated.txt")[0],
+            generated
+        );
+        assert_eq!(config.read("day4", "generated.txt").unwrap(), "XMAS\n");
+        // the input directory is tried first, the files in src stay reachable
+        assert!(config.read("day4", "test.txt").is_ok());
+
+        let absolute = InputConfig::default();
+        assert_eq!(
+            absolute.read("day4", generated.to_str().unwrap()).unwrap(),
+            "XMAS\n"
+        );
+    }
+
+    #[test]
+    fn test_read_from_reader() {
+        assert_eq!(read_from(&b"1|2\n"[..], Path::new(STDIN)).unwrap(), "1|2\n");
+        assert!(matches!(
+            read_from(&b""[..], Path::new(STDIN)),
+            Err(InputError::Empty { .. })
+        ));
+    }
 }